@@ -0,0 +1,19 @@
+#[macro_use]
+extern crate criterion;
+
+use criterion::Criterion;
+use sharpen::*;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let input: Vec<u8> = (0..64 * 1024).map(|i| ((i / 300) % 2) as u8).collect();
+    let input2 = input.clone();
+    c.bench_function("byte_runs 64k", move |b| {
+        b.iter(|| byte_runs(&input).count())
+    });
+    c.bench_function("classify bytes 64k", move |b| {
+        b.iter(|| classify_as_vec(input2.iter(), |&&x| x).len())
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -25,7 +25,7 @@ impl CplxElement {
 impl Node for CplxElement {
     type ChildrenIter = std::vec::IntoIter<CplxElement>;
     fn take_children(&mut self) -> Self::ChildrenIter {
-        core::mem::replace(&mut self.children, vec![]).into_iter()
+        std::mem::take(&mut self.children).into_iter()
     }
 }
 
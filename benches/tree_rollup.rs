@@ -46,25 +46,24 @@ impl tree_rollup::ReversableNode for CplxElement {
 
 fn criterion_benchmark(c: &mut Criterion) {
     let input = vec![Element(0), Element(1), Element(2), Element(3), Element(4)];
-    let mapping = vec![(1, 0), (3, 2), (4, 1)];
+    let mapping = [(1, 0), (3, 2), (4, 1)];
 
     c.bench_function("tree-rollup 5-2", move |b| {
         b.iter(|| {
-            let _result: Vec<_> = rollup_tree(input.clone().into_iter(), mapping.iter().copied())
+            let _result: Vec<_> = rollup_tree(input.clone(), mapping.iter().copied())
                 .expect("valid mapping")
                 .collect();
         })
     });
 
     let input = vec![Element(0), Element(1), Element(2), Element(3), Element(4)];
-    let mapping = vec![(0, 1), (2, 3), (1, 4)];
+    let mapping = [(0, 1), (2, 3), (1, 4)];
 
     c.bench_function("tree-rollup-bottomup 5-2", move |b| {
         b.iter(|| {
-            let _result: Vec<_> =
-                rollup_tree_bottomup(input.clone().into_iter(), mapping.iter().copied())
-                    .expect("valid mapping")
-                    .collect();
+            let _result: Vec<_> = rollup_tree_bottomup(input.clone(), mapping.iter().copied())
+                .expect("valid mapping")
+                .collect();
         })
     });
 
@@ -79,12 +78,11 @@ fn criterion_benchmark(c: &mut Criterion) {
         CplxElement::new(7),
         CplxElement::new(8),
     ];
-    let mapping = vec![(1, 0), (2, 0), (4, 3), (5, 3), (6, 4), (7, 3)];
+    let mapping = [(1, 0), (2, 0), (4, 3), (5, 3), (6, 4), (7, 3)];
 
     c.bench_function("tree-rollup-cplx 9-3", move |b| {
         b.iter_with_large_drop(|| {
-            rollup_tree(input.clone().into_iter(), mapping.iter().copied())
-                .expect("invalid mapping")
+            rollup_tree(input.clone(), mapping.iter().copied()).expect("invalid mapping")
         })
     });
 
@@ -99,12 +97,11 @@ fn criterion_benchmark(c: &mut Criterion) {
         CplxElement::new(7),
         CplxElement::new(8),
     ];
-    let mapping = vec![(0, 2), (1, 2), (3, 5), (4, 5), (5, 7), (6, 7)];
+    let mapping = [(0, 2), (1, 2), (3, 5), (4, 5), (5, 7), (6, 7)];
 
     c.bench_function("tree-rollup-bottomup-cplx 9-3", move |b| {
         b.iter_with_large_drop(|| {
-            rollup_tree_bottomup(input.clone().into_iter(), mapping.iter().copied())
-                .expect("invalid mapping")
+            rollup_tree_bottomup(input.clone(), mapping.iter().copied()).expect("invalid mapping")
         })
     });
 }
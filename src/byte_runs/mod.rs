@@ -0,0 +1,62 @@
+use core::{convert::TryInto, mem::size_of};
+
+#[cfg(test)]
+mod tests;
+
+const WORD: usize = size_of::<usize>();
+
+/// Find the length of the run of `byte` at the start of `input`,
+/// comparing a machine word at a time.
+fn run_len(input: &[u8], byte: u8) -> usize {
+    let pattern = usize::from_le_bytes([byte; WORD]);
+    let mut chunks = input.chunks_exact(WORD);
+    let mut pos = 0;
+
+    for chunk in &mut chunks {
+        let diff = usize::from_le_bytes(chunk.try_into().unwrap()) ^ pattern;
+        if diff != 0 {
+            // little-endian: the lowest set bit belongs to the first differing byte
+            return pos + (diff.trailing_zeros() / 8) as usize;
+        }
+        pos += WORD;
+    }
+
+    let rem = chunks.remainder();
+    pos + rem.iter().position(|&x| x != byte).unwrap_or(rem.len())
+}
+
+/// Iterator over runs of identical bytes, yielding the byte value and the run.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct ByteRunsIT<'s> {
+    rest: &'s [u8],
+}
+
+impl<'s> Iterator for ByteRunsIT<'s> {
+    type Item = (u8, &'s [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&byte, tail) = self.rest.split_first()?;
+        let (run, rest) = self.rest.split_at(1 + run_len(tail, byte));
+        self.rest = rest;
+        Some((byte, run))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::from(!self.rest.is_empty()), Some(self.rest.len()))
+    }
+}
+
+impl core::iter::FusedIterator for ByteRunsIT<'_> {}
+
+/**
+Split a byte slice into runs of identical bytes (e.g. for run-length encoding).
+
+This produces the same groups as `classify(input, |&x| x)`,
+but finds the run boundaries by comparing whole machine words.
+**/
+#[inline]
+pub fn byte_runs(input: &[u8]) -> ByteRunsIT<'_> {
+    ByteRunsIT { rest: input }
+}
@@ -0,0 +1,44 @@
+use super::*;
+use crate::{classify_as_vec, test_util::XorShift};
+use alloc::vec::Vec;
+
+fn check_eq_classify(input: &[u8]) {
+    let expected = classify_as_vec(input.iter().copied(), |&x| x);
+    let res: Vec<_> = byte_runs(input).map(|(b, run)| (b, run.to_vec())).collect();
+    assert_eq!(res, expected, "input = {:?}", input);
+}
+
+#[test]
+fn test_byte_runs() {
+    let input: &[u8] = &[0, 0, 1, 1, 2, 2, 3, 0, 5, 5, 5];
+    let res: Vec<_> = byte_runs(input).collect();
+    assert_eq!(
+        res,
+        &[
+            (0, &[0, 0][..]),
+            (1, &[1, 1][..]),
+            (2, &[2, 2][..]),
+            (3, &[3][..]),
+            (0, &[0][..]),
+            (5, &[5, 5, 5][..]),
+        ]
+    );
+    assert_eq!(byte_runs(&[]).next(), None);
+}
+
+#[test]
+fn test_byte_runs_eq_classify() {
+    // run lengths around and across word boundaries
+    let mut input = Vec::new();
+    for (i, len) in (0..40).chain([63, 64, 65, 200].iter().copied()).enumerate() {
+        input.extend(core::iter::repeat_n((i % 3) as u8, len));
+        check_eq_classify(&input);
+    }
+
+    // pseudo-random data with short runs
+    let mut rng = XorShift::new(0x2545_f491);
+    let noisy: Vec<u8> = (0..1000).map(|_| (rng.next_u32() & 3) as u8).collect();
+    for start in 0..WORD {
+        check_eq_classify(&noisy[start..]);
+    }
+}
@@ -74,11 +74,495 @@ where
 {
 }
 
+/// Context passed to the classification function of [`ClassifyExtIT`]
+#[derive(Debug, PartialEq, Eq)]
+pub struct ClassifyContext<'c, TC> {
+    /// index of the current element inside the input
+    pub index: usize,
+    /// class of the previous element, `None` for the first element
+    pub prev: Option<&'c TC>,
+    /// number of elements already inside the current group
+    pub group_len: usize,
+}
+
+impl<TC> Clone for ClassifyContext<'_, TC> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<TC> Copy for ClassifyContext<'_, TC> {}
+
+/// Like [`ClassifyIT`], but the classification function
+/// additionally receives a [`ClassifyContext`].
+#[derive(Debug, Eq, PartialEq)]
+#[must_use]
+pub struct ClassifyExtIT<'a, TT: 'a, TC, FnT, IT: ?Sized> {
+    inner: &'a mut IT,
+    fnx: FnT,
+    index: usize,
+    edge: Option<(TC, TT)>,
+    finished: bool,
+}
+
+impl<'a, TT: 'a, TC, FnT, IT> ClassifyExtIT<'a, TT, TC, FnT, IT>
+where
+    FnT: FnMut(ClassifyContext<'_, TC>, &TT) -> TC,
+    IT: Iterator<Item = TT>,
+{
+    #[inline]
+    pub fn new(inner: &'a mut IT, fnx: FnT) -> Self {
+        Self {
+            inner,
+            fnx,
+            index: 0,
+            edge: None,
+            finished: false,
+        }
+    }
+
+    fn classify_next(&mut self, x: &TT, prev: Option<&TC>, group_len: usize) -> TC {
+        let ctx = ClassifyContext {
+            index: self.index,
+            prev,
+            group_len,
+        };
+        self.index += 1;
+        (self.fnx)(ctx, x)
+    }
+}
+
+impl<TT, TC, FnT, IT> Iterator for ClassifyExtIT<'_, TT, TC, FnT, IT>
+where
+    TC: PartialEq,
+    FnT: FnMut(ClassifyContext<'_, TC>, &TT) -> TC,
+    IT: Iterator<Item = TT>,
+{
+    type Item = (TC, Vec<TT>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let (ccl, mut last) = match self.edge.take() {
+            Some((ccl, x)) => (ccl, vec![x]),
+            None => match self.inner.next() {
+                Some(x) => (self.classify_next(&x, None, 0), vec![x]),
+                None => {
+                    self.finished = true;
+                    return None;
+                }
+            },
+        };
+
+        while let Some(x) = self.inner.next() {
+            let new_ccl = self.classify_next(&x, Some(&ccl), last.len());
+            if new_ccl == ccl {
+                last.push(x);
+            } else {
+                self.edge = Some((new_ccl, x));
+                return Some((ccl, last));
+            }
+        }
+
+        // we reached the end of the inner iterator
+        self.finished = true;
+        Some((ccl, last))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            return (0, Some(0));
+        }
+        let (_, upper) = self.inner.size_hint();
+        let pending = usize::from(self.edge.is_some());
+        (pending, upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+impl<TT, TC, FnT, IT> core::iter::FusedIterator for ClassifyExtIT<'_, TT, TC, FnT, IT>
+where
+    TC: PartialEq,
+    FnT: FnMut(ClassifyContext<'_, TC>, &TT) -> TC,
+    IT: Iterator<Item = TT>,
+{
+}
+
+/// Classes which can be merged with compatible classes,
+/// used by [`ClassifyMergeIT`] instead of bare [`PartialEq`].
+pub trait ClassMerge: Sized {
+    /// Try to merge the class `other` of a new element into the class `self`
+    /// of the current run, possibly refining `self`.
+    ///
+    /// Return value:
+    /// * Ok: `other` is compatible, the element belongs to the current run.
+    /// * Err(other): `other` is incompatible, the element starts a new run.
+    fn merge(&mut self, other: Self) -> Result<(), Self>;
+}
+
+/// `None` is compatible with any class, `Some` values are compatible if they are equal.
+impl<T: PartialEq> ClassMerge for Option<T> {
+    fn merge(&mut self, other: Self) -> Result<(), Self> {
+        match (&*self, &other) {
+            (_, None) => Ok(()),
+            (None, Some(_)) => {
+                *self = other;
+                Ok(())
+            }
+            (Some(x), Some(y)) if x == y => Ok(()),
+            _ => Err(other),
+        }
+    }
+}
+
+/// Like [`ClassifyIT`], but classes are combined using [`ClassMerge`],
+/// and each group is reported with its final merged class.
+#[derive(Debug, Eq, PartialEq)]
+#[must_use]
+pub struct ClassifyMergeIT<'a, TT: 'a, TC, FnT, IT: ?Sized> {
+    inner: &'a mut IT,
+    fnx: FnT,
+    edge: Option<(TC, TT)>,
+    finished: bool,
+}
+
+impl<'a, TT: 'a, TC, FnT, IT> ClassifyMergeIT<'a, TT, TC, FnT, IT>
+where
+    TC: ClassMerge,
+    FnT: FnMut(&TT) -> TC,
+    IT: Iterator<Item = TT>,
+{
+    #[inline]
+    pub fn new(inner: &'a mut IT, fnx: FnT) -> Self {
+        Self {
+            inner,
+            fnx,
+            edge: None,
+            finished: false,
+        }
+    }
+}
+
+impl<TT, TC, FnT, IT> Iterator for ClassifyMergeIT<'_, TT, TC, FnT, IT>
+where
+    TC: ClassMerge,
+    FnT: FnMut(&TT) -> TC,
+    IT: Iterator<Item = TT>,
+{
+    type Item = (TC, Vec<TT>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let (mut ccl, mut last) = match self.edge.take() {
+            Some((ccl, x)) => (ccl, vec![x]),
+            None => match self.inner.next() {
+                Some(x) => ((self.fnx)(&x), vec![x]),
+                None => {
+                    self.finished = true;
+                    return None;
+                }
+            },
+        };
+
+        for x in &mut self.inner {
+            match ccl.merge((self.fnx)(&x)) {
+                Ok(()) => last.push(x),
+                Err(new_ccl) => {
+                    self.edge = Some((new_ccl, x));
+                    return Some((ccl, last));
+                }
+            }
+        }
+
+        // we reached the end of the inner iterator
+        self.finished = true;
+        Some((ccl, last))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            return (0, Some(0));
+        }
+        let (_, upper) = self.inner.size_hint();
+        let pending = usize::from(self.edge.is_some());
+        (pending, upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+impl<TT, TC, FnT, IT> core::iter::FusedIterator for ClassifyMergeIT<'_, TT, TC, FnT, IT>
+where
+    TC: ClassMerge,
+    FnT: FnMut(&TT) -> TC,
+    IT: Iterator<Item = TT>,
+{
+}
+
+/// Reference point used by [`ClassifyFuzzyIT`] to decide if an element belongs to the current group
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FuzzyMode {
+    /// element must be within epsilon of the first element of the group
+    Anchor,
+    /// element must be within epsilon of the previous element
+    Chain,
+}
+
+/// A group produced by [`ClassifyFuzzyIT`], with the key range of its elements
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FuzzyGroup<K, TT> {
+    pub min: K,
+    pub max: K,
+    pub elems: Vec<TT>,
+}
+
+/// Groups elements whose keys are within `epsilon` of each other,
+/// measured by a distance function, see [`FuzzyMode`].
+#[must_use]
+pub struct ClassifyFuzzyIT<'a, TT: 'a, K, D, FnK, FnD, IT: ?Sized> {
+    inner: &'a mut IT,
+    key: FnK,
+    dist: FnD,
+    epsilon: D,
+    mode: FuzzyMode,
+    edge: Option<(K, TT)>,
+    finished: bool,
+}
+
+impl<'a, TT: 'a, K, D, FnK, FnD, IT> ClassifyFuzzyIT<'a, TT, K, D, FnK, FnD, IT>
+where
+    K: Clone + PartialOrd,
+    D: PartialOrd,
+    FnK: FnMut(&TT) -> K,
+    FnD: FnMut(&K, &K) -> D,
+    IT: Iterator<Item = TT>,
+{
+    #[inline]
+    pub fn new(inner: &'a mut IT, mode: FuzzyMode, epsilon: D, key: FnK, dist: FnD) -> Self {
+        Self {
+            inner,
+            key,
+            dist,
+            epsilon,
+            mode,
+            edge: None,
+            finished: false,
+        }
+    }
+}
+
+impl<TT, K, D, FnK, FnD, IT> Iterator for ClassifyFuzzyIT<'_, TT, K, D, FnK, FnD, IT>
+where
+    K: Clone + PartialOrd,
+    D: PartialOrd,
+    FnK: FnMut(&TT) -> K,
+    FnD: FnMut(&K, &K) -> D,
+    IT: Iterator<Item = TT>,
+{
+    type Item = FuzzyGroup<K, TT>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let (first, x) = match self.edge.take() {
+            Some(edge) => edge,
+            None => match self.inner.next() {
+                Some(x) => ((self.key)(&x), x),
+                None => {
+                    self.finished = true;
+                    return None;
+                }
+            },
+        };
+        let mut ret = FuzzyGroup {
+            min: first.clone(),
+            max: first.clone(),
+            elems: vec![x],
+        };
+        let mut prev = first.clone();
+
+        for x in &mut self.inner {
+            let k = (self.key)(&x);
+            let reference = match self.mode {
+                FuzzyMode::Anchor => &first,
+                FuzzyMode::Chain => &prev,
+            };
+            if (self.dist)(reference, &k) > self.epsilon {
+                self.edge = Some((k, x));
+                return Some(ret);
+            }
+            if k < ret.min {
+                ret.min = k.clone();
+            } else if k > ret.max {
+                ret.max = k.clone();
+            }
+            ret.elems.push(x);
+            prev = k;
+        }
+
+        // we reached the end of the inner iterator
+        self.finished = true;
+        Some(ret)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            return (0, Some(0));
+        }
+        let (_, upper) = self.inner.size_hint();
+        let pending = usize::from(self.edge.is_some());
+        (pending, upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+impl<TT, K, D, FnK, FnD, IT> core::iter::FusedIterator
+    for ClassifyFuzzyIT<'_, TT, K, D, FnK, FnD, IT>
+where
+    K: Clone + PartialOrd,
+    D: PartialOrd,
+    FnK: FnMut(&TT) -> K,
+    FnD: FnMut(&K, &K) -> D,
+    IT: Iterator<Item = TT>,
+{
+}
+
+/// A group produced by [`ClassifyBoundedIT`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoundedGroup<TC, TT> {
+    pub class: TC,
+    pub elems: Vec<TT>,
+    /// `true` if this group continues the run of the previous group,
+    /// which was split because it exceeded the maximum size
+    pub continued: bool,
+}
+
+/// Like [`ClassifyIT`], but runs whose total weight exceeds a maximum
+/// are split into multiple groups.
+#[derive(Debug, Eq, PartialEq)]
+#[must_use]
+pub struct ClassifyBoundedIT<'a, TT: 'a, TC, FnT, FnW, IT: ?Sized> {
+    inner: &'a mut IT,
+    fnx: FnT,
+    weight: FnW,
+    max_weight: usize,
+    edge: Option<(TC, TT, bool)>,
+    finished: bool,
+}
+
+impl<'a, TT: 'a, TC, FnT, FnW, IT> ClassifyBoundedIT<'a, TT, TC, FnT, FnW, IT>
+where
+    TC: PartialEq,
+    FnT: FnMut(&TT) -> TC,
+    FnW: FnMut(&TT) -> usize,
+    IT: Iterator<Item = TT>,
+{
+    /// Groups have a total weight of at most `max_weight`,
+    /// except if a single element is heavier than that.
+    #[inline]
+    pub fn new(inner: &'a mut IT, max_weight: usize, fnx: FnT, weight: FnW) -> Self {
+        Self {
+            inner,
+            fnx,
+            weight,
+            max_weight,
+            edge: None,
+            finished: false,
+        }
+    }
+}
+
+impl<TT, TC, FnT, FnW, IT> Iterator for ClassifyBoundedIT<'_, TT, TC, FnT, FnW, IT>
+where
+    TC: PartialEq,
+    FnT: FnMut(&TT) -> TC,
+    FnW: FnMut(&TT) -> usize,
+    IT: Iterator<Item = TT>,
+{
+    type Item = BoundedGroup<TC, TT>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let (class, x, continued) = match self.edge.take() {
+            Some(edge) => edge,
+            None => match self.inner.next() {
+                Some(x) => ((self.fnx)(&x), x, false),
+                None => {
+                    self.finished = true;
+                    return None;
+                }
+            },
+        };
+        let mut total = (self.weight)(&x);
+        let mut ret = BoundedGroup {
+            class,
+            elems: vec![x],
+            continued,
+        };
+
+        for x in &mut self.inner {
+            let ccl = (self.fnx)(&x);
+            if ccl != ret.class {
+                self.edge = Some((ccl, x, false));
+                return Some(ret);
+            }
+            total = total.saturating_add((self.weight)(&x));
+            if total > self.max_weight {
+                self.edge = Some((ccl, x, true));
+                return Some(ret);
+            }
+            ret.elems.push(x);
+        }
+
+        // we reached the end of the inner iterator
+        self.finished = true;
+        Some(ret)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            return (0, Some(0));
+        }
+        let (_, upper) = self.inner.size_hint();
+        let pending = usize::from(self.edge.is_some());
+        (pending, upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+impl<TT, TC, FnT, FnW, IT> core::iter::FusedIterator for ClassifyBoundedIT<'_, TT, TC, FnT, FnW, IT>
+where
+    TC: PartialEq,
+    FnT: FnMut(&TT) -> TC,
+    FnW: FnMut(&TT) -> usize,
+    IT: Iterator<Item = TT>,
+{
+}
+
 pub trait Classify<'a, TT: 'a>: Iterator<Item = TT> + 'a {
     fn classify<TC, FnT>(&'a mut self, fnx: FnT) -> ClassifyIT<'a, TT, TC, FnT, Self>
     where
         TC: Default + PartialEq,
         FnT: FnMut(&TT) -> TC;
+
+    fn classify_ext<TC, FnT>(&'a mut self, fnx: FnT) -> ClassifyExtIT<'a, TT, TC, FnT, Self>
+    where
+        TC: PartialEq,
+        FnT: FnMut(ClassifyContext<'_, TC>, &TT) -> TC;
+
+    fn classify_merge<TC, FnT>(&'a mut self, fnx: FnT) -> ClassifyMergeIT<'a, TT, TC, FnT, Self>
+    where
+        TC: ClassMerge,
+        FnT: FnMut(&TT) -> TC;
 }
 
 impl<'a, IT, TT: 'a> Classify<'a, TT> for IT
@@ -93,6 +577,24 @@ where
     {
         ClassifyIT::new(self, fnx)
     }
+
+    #[inline]
+    fn classify_ext<TC, FnT>(&'a mut self, fnx: FnT) -> ClassifyExtIT<'a, TT, TC, FnT, Self>
+    where
+        TC: PartialEq,
+        FnT: FnMut(ClassifyContext<'_, TC>, &TT) -> TC,
+    {
+        ClassifyExtIT::new(self, fnx)
+    }
+
+    #[inline]
+    fn classify_merge<TC, FnT>(&'a mut self, fnx: FnT) -> ClassifyMergeIT<'a, TT, TC, FnT, Self>
+    where
+        TC: ClassMerge,
+        FnT: FnMut(&TT) -> TC,
+    {
+        ClassifyMergeIT::new(self, fnx)
+    }
 }
 
 #[inline]
@@ -116,3 +618,85 @@ where
 {
     classify(input, fnx)
 }
+
+/// Like [`classify`], but the classification function
+/// additionally receives a [`ClassifyContext`].
+#[inline]
+pub fn classify_ext<Input, TT, TC, TRes>(
+    input: Input,
+    fnx: impl FnMut(ClassifyContext<'_, TC>, &TT) -> TC,
+) -> TRes
+where
+    Input: IntoIterator<Item = TT>,
+    TC: PartialEq,
+    TRes: core::iter::FromIterator<(TC, Vec<TT>)>,
+{
+    input.into_iter().classify_ext(fnx).collect()
+}
+
+/// Like [`classify`], but classes are combined using [`ClassMerge`].
+#[inline]
+pub fn classify_merge<Input, TT, TC, TRes>(input: Input, fnx: impl FnMut(&TT) -> TC) -> TRes
+where
+    Input: IntoIterator<Item = TT>,
+    TC: ClassMerge,
+    TRes: core::iter::FromIterator<(TC, Vec<TT>)>,
+{
+    input.into_iter().classify_merge(fnx).collect()
+}
+
+/**
+Group adjacent elements whose keys are within `epsilon` of each other,
+either relative to the first element of the group ([`FuzzyMode::Anchor`])
+or to the previous element ([`FuzzyMode::Chain`]).
+
+`dist` is called with the reference key and the key of the new element.
+**/
+#[inline]
+pub fn classify_fuzzy<Input, TT, K, D, TRes>(
+    input: Input,
+    mode: FuzzyMode,
+    epsilon: D,
+    key: impl FnMut(&TT) -> K,
+    dist: impl FnMut(&K, &K) -> D,
+) -> TRes
+where
+    Input: IntoIterator<Item = TT>,
+    K: Clone + PartialOrd,
+    D: PartialOrd,
+    TRes: core::iter::FromIterator<FuzzyGroup<K, TT>>,
+{
+    ClassifyFuzzyIT::new(&mut input.into_iter(), mode, epsilon, key, dist).collect()
+}
+
+/// Like [`classify`], but runs are split into groups of at most `max_len` elements.
+#[inline]
+pub fn classify_bounded<Input, TT, TC, TRes>(
+    input: Input,
+    max_len: usize,
+    fnx: impl FnMut(&TT) -> TC,
+) -> TRes
+where
+    Input: IntoIterator<Item = TT>,
+    TC: PartialEq,
+    TRes: core::iter::FromIterator<BoundedGroup<TC, TT>>,
+{
+    classify_weighted(input, max_len, fnx, |_| 1)
+}
+
+/// Like [`classify`], but runs are split into groups
+/// with a total `weight` of at most `max_weight`.
+#[inline]
+pub fn classify_weighted<Input, TT, TC, TRes>(
+    input: Input,
+    max_weight: usize,
+    fnx: impl FnMut(&TT) -> TC,
+    weight: impl FnMut(&TT) -> usize,
+) -> TRes
+where
+    Input: IntoIterator<Item = TT>,
+    TC: PartialEq,
+    TRes: core::iter::FromIterator<BoundedGroup<TC, TT>>,
+{
+    ClassifyBoundedIT::new(&mut input.into_iter(), max_weight, fnx, weight).collect()
+}
@@ -84,3 +84,272 @@ fn test_clsfit2() {
         ]
     );
 }
+
+#[test]
+fn test_clsf_ext_index() {
+    let input: Vec<u8> = vec![1, 1, 1, 1, 1, 2, 2];
+    // split runs into chunks of at most 2 elements, keyed by the chunk start
+    let res: Vec<_> = classify_ext(input, |ctx, &curc| match ctx.prev {
+        Some(&(prev_start, prev_c)) if prev_c == curc && ctx.group_len < 2 => (prev_start, curc),
+        _ => (ctx.index, curc),
+    });
+    assert_eq!(
+        res,
+        &[
+            ((0, 1), vec![1, 1]),
+            ((2, 1), vec![1, 1]),
+            ((4, 1), vec![1]),
+            ((5, 2), vec![2, 2]),
+        ]
+    );
+}
+
+#[test]
+fn test_clsf_ext_prev() {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Block {
+        Heading,
+        Paragraph,
+        Empty,
+    }
+
+    let input = vec!["# title", "some", "  continued", "", "  indented"];
+    let res: Vec<_> = classify_ext(input, |ctx, line: &&str| {
+        if line.is_empty() {
+            Block::Empty
+        } else if line.starts_with('#') {
+            Block::Heading
+        } else if line.starts_with(' ') && ctx.prev == Some(&Block::Paragraph) {
+            Block::Paragraph
+        } else if line.starts_with(' ') {
+            Block::Empty
+        } else {
+            Block::Paragraph
+        }
+    });
+    assert_eq!(
+        res,
+        &[
+            (Block::Heading, vec!["# title"]),
+            (Block::Paragraph, vec!["some", "  continued"]),
+            (Block::Empty, vec!["", "  indented"]),
+        ]
+    );
+}
+
+#[test]
+fn test_clsfit_ext_empty() {
+    let mut input = core::iter::empty::<u8>();
+    let mut it = ClassifyExtIT::new(&mut input, |_, &x| x);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn test_clsf_merge_option() {
+    let input: Vec<Option<u8>> = vec![None, Some(1), None, Some(1), Some(2), None, None];
+    let res: Vec<_> = classify_merge(input, |&x| x);
+    assert_eq!(
+        res,
+        &[
+            (Some(1), vec![None, Some(1), None, Some(1)]),
+            (Some(2), vec![Some(2), None, None]),
+        ]
+    );
+}
+
+#[test]
+fn test_clsf_merge_lattice() {
+    #[derive(Debug, PartialEq, Eq)]
+    enum Tok {
+        Unknown,
+        Word,
+        Num,
+    }
+
+    impl ClassMerge for Tok {
+        fn merge(&mut self, other: Self) -> Result<(), Self> {
+            match (&*self, other) {
+                (_, Tok::Unknown) => Ok(()),
+                (Tok::Unknown, other) => {
+                    *self = other;
+                    Ok(())
+                }
+                (Tok::Word, Tok::Word) | (Tok::Num, Tok::Num) => Ok(()),
+                (_, other) => Err(other),
+            }
+        }
+    }
+
+    let input = vec!['_', 'a', '_', 'b', '1', '_', '2', '_'];
+    let res: Vec<_> = classify_merge(input, |c: &char| {
+        if c.is_alphabetic() {
+            Tok::Word
+        } else if c.is_numeric() {
+            Tok::Num
+        } else {
+            Tok::Unknown
+        }
+    });
+    assert_eq!(
+        res,
+        &[
+            (Tok::Word, vec!['_', 'a', '_', 'b']),
+            (Tok::Num, vec!['1', '_', '2', '_']),
+        ]
+    );
+}
+
+#[test]
+fn test_clsf_fuzzy() {
+    let input: Vec<i32> = vec![10, 11, 12, 13, 20, 21, 19, 40];
+    let dist = |a: &i32, b: &i32| (a - b).abs();
+
+    let res: Vec<_> = classify_fuzzy(input.clone(), FuzzyMode::Anchor, 2, |&x| x, dist);
+    assert_eq!(
+        res,
+        &[
+            FuzzyGroup {
+                min: 10,
+                max: 12,
+                elems: vec![10, 11, 12],
+            },
+            FuzzyGroup {
+                min: 13,
+                max: 13,
+                elems: vec![13],
+            },
+            FuzzyGroup {
+                min: 19,
+                max: 21,
+                elems: vec![20, 21, 19],
+            },
+            FuzzyGroup {
+                min: 40,
+                max: 40,
+                elems: vec![40],
+            },
+        ]
+    );
+
+    // chains may drift further than epsilon, which is visible in min/max
+    let res: Vec<_> = classify_fuzzy(input, FuzzyMode::Chain, 2, |&x| x, dist);
+    assert_eq!(
+        res,
+        &[
+            FuzzyGroup {
+                min: 10,
+                max: 13,
+                elems: vec![10, 11, 12, 13],
+            },
+            FuzzyGroup {
+                min: 19,
+                max: 21,
+                elems: vec![20, 21, 19],
+            },
+            FuzzyGroup {
+                min: 40,
+                max: 40,
+                elems: vec![40],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_clsf_fuzzy_float() {
+    let input = vec![(0u8, 1.0f64), (1, 1.05), (2, 1.2), (3, 1.3)];
+    let res: Vec<FuzzyGroup<f64, (u8, f64)>> = classify_fuzzy(
+        input,
+        FuzzyMode::Chain,
+        0.11,
+        |&(_, v)| v,
+        |a: &f64, b: &f64| (a - b).abs(),
+    );
+    let ids: Vec<Vec<u8>> = res
+        .iter()
+        .map(|g| g.elems.iter().map(|&(id, _)| id).collect())
+        .collect();
+    assert_eq!(ids, vec![vec![0, 1], vec![2, 3]]);
+}
+
+#[test]
+fn test_clsf_bounded() {
+    let input: Vec<u8> = vec![0, 0, 0, 0, 0, 1, 0, 0];
+    let res: Vec<_> = classify_bounded(input, 2, |&curc| curc);
+    assert_eq!(
+        res,
+        &[
+            BoundedGroup {
+                class: 0,
+                elems: vec![0, 0],
+                continued: false,
+            },
+            BoundedGroup {
+                class: 0,
+                elems: vec![0, 0],
+                continued: true,
+            },
+            BoundedGroup {
+                class: 0,
+                elems: vec![0],
+                continued: true,
+            },
+            BoundedGroup {
+                class: 1,
+                elems: vec![1],
+                continued: false,
+            },
+            BoundedGroup {
+                class: 0,
+                elems: vec![0, 0],
+                continued: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_clsf_weighted() {
+    let input = vec!["ab", "cde", "f", "overlong", "g", ""];
+    let res: Vec<BoundedGroup<bool, &str>> =
+        classify_weighted(input, 4, |s| !s.is_empty(), |s| s.len());
+    let res: Vec<_> = res
+        .into_iter()
+        .map(|g| (g.class, g.elems, g.continued))
+        .collect();
+    assert_eq!(
+        res,
+        &[
+            (true, vec!["ab"], false),
+            (true, vec!["cde", "f"], true),
+            (true, vec!["overlong"], true),
+            (true, vec!["g"], true),
+            (false, vec![""], false),
+        ]
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_clsf_serde() {
+    let res: Vec<BoundedGroup<u8, u8>> = classify_bounded(vec![0u8, 0, 0, 1], 2, |&x| x);
+    let json = serde_json::to_string(&res).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Vec<BoundedGroup<u8, u8>>>(&json).unwrap(),
+        res
+    );
+
+    let res: Vec<FuzzyGroup<u8, u8>> = classify_fuzzy(
+        vec![0u8, 1, 5],
+        FuzzyMode::Chain,
+        1,
+        |&x| x,
+        |a, b| a.abs_diff(*b),
+    );
+    let json = serde_json::to_string(&res).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Vec<FuzzyGroup<u8, u8>>>(&json).unwrap(),
+        res
+    );
+}
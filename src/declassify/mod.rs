@@ -0,0 +1,77 @@
+use alloc::vec::Vec;
+
+#[cfg(test)]
+mod tests;
+
+/**
+Flatten classified groups back into a stream of tagged elements.
+This is the opposite function of [`classify`](crate::classify_::classify).
+
+The class of each group is cloned once per element.
+**/
+#[inline]
+pub fn declassify<Input, TT, TC>(input: Input) -> impl Iterator<Item = (TC, TT)>
+where
+    Input: IntoIterator<Item = (TC, Vec<TT>)>,
+    TC: Clone,
+{
+    input
+        .into_iter()
+        .flat_map(|(ccl, elems)| elems.into_iter().map(move |x| (ccl.clone(), x)))
+}
+
+/**
+Flatten borrowed classified groups into a stream of tagged elements,
+without cloning the classes.
+**/
+#[inline]
+pub fn declassify_ref<'a, Input, TT, TC>(input: Input) -> impl Iterator<Item = (&'a TC, &'a TT)>
+where
+    Input: IntoIterator<Item = &'a (TC, Vec<TT>)>,
+    TT: 'a,
+    TC: 'a,
+{
+    input
+        .into_iter()
+        .flat_map(|(ccl, elems)| elems.iter().map(move |x| (ccl, x)))
+}
+
+/**
+Re-run the grouping on already classified (and possibly edited) groups.
+
+Adjacent groups with equal classes are merged and empty groups are dropped,
+thus the result is the same as if the flattened elements would have been
+classified again with their current classes.
+**/
+pub fn reclassify<Input, TT, TC, TRes>(input: Input) -> TRes
+where
+    Input: IntoIterator<Item = (TC, Vec<TT>)>,
+    TC: PartialEq,
+    TRes: core::iter::FromIterator<(TC, Vec<TT>)>,
+{
+    let mut ret: Vec<(TC, Vec<TT>)> = Vec::new();
+    for (ccl, mut elems) in input {
+        if elems.is_empty() {
+            continue;
+        }
+        match ret.last_mut() {
+            Some((last_ccl, last)) if *last_ccl == ccl => last.append(&mut elems),
+            _ => ret.push((ccl, elems)),
+        }
+    }
+    ret.into_iter().collect()
+}
+
+/**
+Flatten classified groups and classify the elements again,
+using a new classification function.
+**/
+#[inline]
+pub fn reclassify_with<Input, TT, TC, TC2, TRes>(input: Input, fnx: impl FnMut(&TT) -> TC2) -> TRes
+where
+    Input: IntoIterator<Item = (TC, Vec<TT>)>,
+    TC2: Default + PartialEq,
+    TRes: core::iter::FromIterator<(TC2, Vec<TT>)>,
+{
+    crate::classify(input.into_iter().flat_map(|(_, elems)| elems), fnx)
+}
@@ -0,0 +1,48 @@
+use super::*;
+use crate::classify_as_vec;
+use alloc::{vec, vec::Vec};
+
+#[test]
+fn test_declassify() {
+    let input: Vec<u8> = vec![0, 0, 1, 1, 2, 3, 0];
+    let classified = classify_as_vec(input.clone(), |&curc| curc % 2 == 0);
+    let res: Vec<_> = declassify(classified).collect();
+    assert_eq!(
+        res,
+        &[
+            (true, 0),
+            (true, 0),
+            (false, 1),
+            (false, 1),
+            (true, 2),
+            (false, 3),
+            (true, 0),
+        ]
+    );
+    assert_eq!(res.into_iter().map(|(_, x)| x).collect::<Vec<_>>(), input);
+}
+
+#[test]
+fn test_declassify_ref() {
+    let classified = vec![(true, vec![0, 2]), (false, vec![1])];
+    let res: Vec<_> = declassify_ref(&classified).collect();
+    assert_eq!(res, &[(&true, &0), (&true, &2), (&false, &1)]);
+}
+
+#[test]
+fn test_reclassify() {
+    let mut classified = classify_as_vec(vec![0u8, 0, 1, 2, 2], |&curc| curc);
+    // edit the groups: move the single `1` into the neighbouring group
+    classified[1].1.clear();
+    classified[0].1.push(1);
+    classified[2].0 = 0;
+    let res: Vec<_> = reclassify(classified);
+    assert_eq!(res, &[(0, vec![0, 0, 1, 2, 2])]);
+}
+
+#[test]
+fn test_reclassify_with() {
+    let classified = vec![(0u8, vec![0u8, 0]), (1, vec![1]), (2, vec![2, 3])];
+    let res: Vec<_> = reclassify_with(classified, |&curc| curc < 2);
+    assert_eq!(res, &[(true, vec![0, 0, 1]), (false, vec![2, 3])]);
+}
@@ -0,0 +1,162 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Connectivity {
+    /// runs are connected if they share an edge
+    Four,
+    /// runs are connected if they share an edge or a corner
+    Eight,
+}
+
+/// A horizontal run of equally classified cells inside one row
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridRun {
+    pub row: usize,
+    pub cols: Range<usize>,
+    /// index into [`GridRegions::regions`]
+    pub region: usize,
+}
+
+/// A connected region of equally classified cells
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridRegion<TC> {
+    pub class: TC,
+    /// bounding box rows
+    pub rows: Range<usize>,
+    /// bounding box columns
+    pub cols: Range<usize>,
+    /// number of cells
+    pub size: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridRegions<TC> {
+    /// all runs, in row-major order
+    pub runs: Vec<GridRun>,
+    /// regions, ordered by their first cell in row-major order
+    pub regions: Vec<GridRegion<TC>>,
+}
+
+fn find_root(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        // path halving
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+fn union(parents: &mut [usize], a: usize, b: usize) {
+    let (a, b) = (find_root(parents, a), find_root(parents, b));
+    // keep the smaller (earlier) run as root
+    if a < b {
+        parents[b] = a;
+    } else {
+        parents[a] = b;
+    }
+}
+
+/**
+Classify each row of a grid into runs (like [`classify`](crate::classify_::classify))
+and merge vertically touching runs of equal class into connected regions.
+
+Rows may have different lengths.
+**/
+pub fn grid_regions<Rows, TT, TC>(
+    rows: Rows,
+    connectivity: Connectivity,
+    mut fnx: impl FnMut(&TT) -> TC,
+) -> GridRegions<TC>
+where
+    Rows: IntoIterator,
+    Rows::Item: IntoIterator<Item = TT>,
+    TC: PartialEq,
+{
+    let mut runs: Vec<GridRun> = Vec::new();
+    let mut classes: Vec<TC> = Vec::new();
+    let mut parents: Vec<usize> = Vec::new();
+    // index range of the runs of the previous row
+    let mut prev_row = 0..0;
+    let slack = match connectivity {
+        Connectivity::Four => 0,
+        Connectivity::Eight => 1,
+    };
+
+    for (row, cells) in rows.into_iter().enumerate() {
+        let cur_start = runs.len();
+
+        for (col, x) in cells.into_iter().enumerate() {
+            let ccl = fnx(&x);
+            if runs.len() > cur_start && *classes.last().unwrap() == ccl {
+                runs.last_mut().unwrap().cols.end = col + 1;
+            } else {
+                parents.push(runs.len());
+                runs.push(GridRun {
+                    row,
+                    cols: col..col + 1,
+                    region: 0,
+                });
+                classes.push(ccl);
+            }
+        }
+
+        // connect with the previous row, both run lists are sorted by column
+        let mut j = prev_row.start;
+        for i in cur_start..runs.len() {
+            let cols = runs[i].cols.clone();
+            // skip runs of the previous row which end before the current one
+            while j < prev_row.end && runs[j].cols.end + slack <= cols.start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < prev_row.end && runs[k].cols.start < cols.end + slack {
+                if classes[k] == classes[i] {
+                    union(&mut parents, i, k);
+                }
+                k += 1;
+            }
+        }
+
+        prev_row = cur_start..runs.len();
+    }
+
+    // assign dense region ids, in order of the first run of each region
+    let mut region_of_root: Vec<Option<usize>> = (0..runs.len()).map(|_| None).collect();
+    let mut regions: Vec<GridRegion<TC>> = Vec::new();
+    for (i, ccl) in classes.into_iter().enumerate() {
+        let root = find_root(&mut parents, i);
+        let run = &mut runs[i];
+        let width = run.cols.end - run.cols.start;
+        match region_of_root[root] {
+            Some(id) => {
+                let reg = &mut regions[id];
+                reg.rows.end = run.row + 1;
+                reg.cols.start = reg.cols.start.min(run.cols.start);
+                reg.cols.end = reg.cols.end.max(run.cols.end);
+                reg.size += width;
+                run.region = id;
+            }
+            None => {
+                let id = regions.len();
+                region_of_root[root] = Some(id);
+                regions.push(GridRegion {
+                    class: ccl,
+                    rows: run.row..run.row + 1,
+                    cols: run.cols.clone(),
+                    size: width,
+                });
+                run.region = id;
+            }
+        }
+    }
+
+    GridRegions { runs, regions }
+}
@@ -0,0 +1,93 @@
+use super::*;
+use alloc::{vec, vec::Vec};
+
+fn region_map<TC>(res: &GridRegions<TC>, height: usize, width: usize) -> Vec<Vec<usize>> {
+    let mut ret = vec![vec![usize::MAX; width]; height];
+    for run in &res.runs {
+        for col in run.cols.clone() {
+            ret[run.row][col] = run.region;
+        }
+    }
+    ret
+}
+
+#[test]
+fn test_grid_regions_four() {
+    let grid = vec![
+        vec![1, 1, 0, 0],
+        vec![0, 1, 0, 1],
+        vec![0, 0, 1, 1],
+        vec![1, 0, 0, 1],
+    ];
+    let res = grid_regions(&grid, Connectivity::Four, |&&x| x);
+    assert_eq!(
+        region_map(&res, 4, 4),
+        vec![
+            vec![0, 0, 1, 1],
+            vec![2, 0, 1, 3],
+            vec![2, 2, 3, 3],
+            vec![4, 2, 2, 3],
+        ]
+    );
+    assert_eq!(
+        res.regions[2],
+        GridRegion {
+            class: 0,
+            rows: 1..4,
+            cols: 0..3,
+            size: 5,
+        }
+    );
+    assert_eq!(
+        res.regions[3],
+        GridRegion {
+            class: 1,
+            rows: 1..4,
+            cols: 2..4,
+            size: 4,
+        }
+    );
+}
+
+#[test]
+fn test_grid_regions_eight() {
+    let grid = vec![
+        vec![1, 1, 0, 0],
+        vec![0, 1, 0, 1],
+        vec![0, 0, 1, 1],
+        vec![1, 0, 0, 1],
+    ];
+    let res = grid_regions(&grid, Connectivity::Eight, |&&x| x);
+    assert_eq!(
+        region_map(&res, 4, 4),
+        vec![
+            vec![0, 0, 1, 1],
+            vec![1, 0, 1, 0],
+            vec![1, 1, 0, 0],
+            vec![2, 1, 1, 0],
+        ]
+    );
+    let sizes: Vec<_> = res.regions.iter().map(|r| r.size).collect();
+    assert_eq!(sizes, &[7, 8, 1]);
+}
+
+#[test]
+fn test_grid_regions_ragged() {
+    let grid: Vec<&str> = vec!["aab", "", "bba"];
+    let res = grid_regions(grid.iter().map(|r| r.chars()), Connectivity::Four, |&c| c);
+    let classes: Vec<_> = res.regions.iter().map(|r| r.class).collect();
+    assert_eq!(classes, &['a', 'b', 'b', 'a']);
+    assert_eq!(res.runs.len(), 4);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_grid_regions_serde() {
+    let grid = vec![vec![1, 1, 0], vec![0, 1, 0]];
+    let res = grid_regions(&grid, Connectivity::Four, |&&x| x);
+    let json = serde_json::to_string(&res).unwrap();
+    assert_eq!(
+        serde_json::from_str::<GridRegions<i32>>(&json).unwrap(),
+        res
+    );
+}
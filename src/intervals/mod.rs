@@ -0,0 +1,168 @@
+use alloc::{vec, vec::Vec};
+use core::ops::{Add, Range};
+
+#[cfg(test)]
+mod tests;
+
+/// Types which can be used as interval bounds
+pub trait IntervalBound: Copy + PartialOrd + Add<Output = Self> {
+    /// The smallest distance between two distinct values,
+    /// used to detect touching [`IntervalKind::Closed`] intervals
+    /// (zero for continuous domains).
+    const STEP: Self;
+
+    /// Addition which returns `None` if the result isn't representable
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_interval_bound {
+    (int: $($t:ty),*) => {
+        $(impl IntervalBound for $t {
+            const STEP: Self = 1;
+
+            #[inline]
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+        })*
+    };
+    (float: $($t:ty),*) => {
+        $(impl IntervalBound for $t {
+            const STEP: Self = 0.0;
+
+            #[inline]
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                Some(self + rhs)
+            }
+        })*
+    };
+}
+
+impl_interval_bound!(int: u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_interval_bound!(float: f32, f64);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IntervalKind {
+    /// `[start, end)`, e.g. [`Range`]; `0..2` and `2..4` touch
+    HalfOpen,
+    /// `[start, end]`; `[0, 2]` and `[3, 4]` touch for integer bounds
+    Closed,
+}
+
+/// A merged interval and the original members it was built from
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoalescedInterval<T, TT> {
+    pub start: T,
+    pub end: T,
+    pub members: Vec<TT>,
+}
+
+/// Iterator which merges adjacent overlapping or touching intervals
+#[must_use]
+pub struct CoalesceIT<T, FnT, IT: Iterator> {
+    inner: IT,
+    fnx: FnT,
+    kind: IntervalKind,
+    tolerance: T,
+    edge: Option<(T, T, IT::Item)>,
+}
+
+impl<T, FnT, IT> Iterator for CoalesceIT<T, FnT, IT>
+where
+    T: IntervalBound,
+    FnT: FnMut(&IT::Item) -> (T, T),
+    IT: Iterator,
+{
+    type Item = CoalescedInterval<T, IT::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end, x) = match self.edge.take() {
+            Some(edge) => edge,
+            None => {
+                let x = self.inner.next()?;
+                let (start, end) = (self.fnx)(&x);
+                (start, end, x)
+            }
+        };
+        let mut ret = CoalescedInterval {
+            start,
+            end,
+            members: vec![x],
+        };
+
+        let tolerance = self.tolerance;
+        for x in &mut self.inner {
+            let (start, end) = (self.fnx)(&x);
+            let reach = match self.kind {
+                IntervalKind::HalfOpen => ret.end.checked_add(tolerance),
+                IntervalKind::Closed => ret
+                    .end
+                    .checked_add(T::STEP)
+                    .and_then(|x| x.checked_add(tolerance)),
+            };
+            // an unrepresentable reach lies beyond every possible start
+            if reach.is_some_and(|reach| start > reach) {
+                self.edge = Some((start, end, x));
+                return Some(ret);
+            }
+            if start < ret.start {
+                ret.start = start;
+            }
+            if end > ret.end {
+                ret.end = end;
+            }
+            ret.members.push(x);
+        }
+        Some(ret)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.inner.size_hint();
+        let pending = usize::from(self.edge.is_some());
+        (pending, upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+/**
+Merge intervals which overlap, touch, or are separated by a gap of at most `tolerance`.
+
+`fnx` extracts the `(start, end)` bounds of each element.
+The input should be sorted by start, otherwise only adjacent intervals are merged
+(like [`classify`](crate::classify_::classify) only groups adjacent elements).
+**/
+#[inline]
+pub fn coalesce_intervals<Input, T, FnT>(
+    input: Input,
+    kind: IntervalKind,
+    tolerance: T,
+    fnx: FnT,
+) -> CoalesceIT<T, FnT, Input::IntoIter>
+where
+    Input: IntoIterator,
+    T: IntervalBound,
+    FnT: FnMut(&Input::Item) -> (T, T),
+{
+    CoalesceIT {
+        inner: input.into_iter(),
+        fnx,
+        kind,
+        tolerance,
+        edge: None,
+    }
+}
+
+/// Merge sorted half-open [`Range`]s which overlap or touch.
+#[inline]
+pub fn coalesce_ranges<Input, T>(input: Input) -> impl Iterator<Item = Range<T>>
+where
+    Input: IntoIterator<Item = Range<T>>,
+    T: IntervalBound + Default,
+{
+    coalesce_intervals(input, IntervalKind::HalfOpen, T::default(), |r| {
+        (r.start, r.end)
+    })
+    .map(|i| i.start..i.end)
+}
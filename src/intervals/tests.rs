@@ -0,0 +1,101 @@
+use super::*;
+use alloc::{vec, vec::Vec};
+
+#[test]
+fn test_coalesce_halfopen() {
+    let input = vec![0..2, 1..3, 3..4, 6..8, 7..7, 10..12, 11..12];
+    let res: Vec<_> =
+        coalesce_intervals(input, IntervalKind::HalfOpen, 0, |r| (r.start, r.end)).collect();
+    assert_eq!(
+        res,
+        &[
+            CoalescedInterval {
+                start: 0,
+                end: 4,
+                members: vec![0..2, 1..3, 3..4],
+            },
+            CoalescedInterval {
+                start: 6,
+                end: 8,
+                members: vec![6..8, 7..7],
+            },
+            CoalescedInterval {
+                start: 10,
+                end: 12,
+                members: vec![10..12, 11..12],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_coalesce_closed_tolerance() {
+    let input = vec![(0u32, 2u32), (3, 4), (6, 8), (11, 12)];
+    let bounds: Vec<_> = coalesce_intervals(input.clone(), IntervalKind::Closed, 0, |&x| x)
+        .map(|i| (i.start, i.end))
+        .collect();
+    assert_eq!(bounds, &[(0, 4), (6, 8), (11, 12)]);
+
+    let bounds: Vec<_> = coalesce_intervals(input, IntervalKind::Closed, 2, |&x| x)
+        .map(|i| (i.start, i.end))
+        .collect();
+    assert_eq!(bounds, &[(0, 12)]);
+}
+
+#[test]
+fn test_coalesce_float() {
+    let input = vec![(0.0, 1.0), (1.0, 1.5), (1.6, 2.0)];
+    let bounds: Vec<_> = coalesce_intervals(input.clone(), IntervalKind::Closed, 0.0, |&x| x)
+        .map(|i| (i.start, i.end))
+        .collect();
+    assert_eq!(bounds, &[(0.0, 1.5), (1.6, 2.0)]);
+
+    let bounds: Vec<_> = coalesce_intervals(input, IntervalKind::HalfOpen, 0.2, |&x| x)
+        .map(|i| (i.start, i.end))
+        .collect();
+    assert_eq!(bounds, &[(0.0, 2.0)]);
+}
+
+#[test]
+fn test_coalesce_ranges() {
+    let res: Vec<_> = coalesce_ranges(vec![0..2, 2..3, 5..6]).collect();
+    assert_eq!(res, &[0..3, 5..6]);
+    assert_eq!(coalesce_ranges(Vec::<Range<u8>>::new()).next(), None);
+}
+
+#[test]
+fn test_coalesce_max_bound() {
+    let bounds: Vec<_> = coalesce_intervals(
+        vec![(250u8, 255u8), (255, 255)],
+        IntervalKind::Closed,
+        0,
+        |&x| x,
+    )
+    .map(|i| (i.start, i.end))
+    .collect();
+    assert_eq!(bounds, &[(250, 255)]);
+
+    let bounds: Vec<_> = coalesce_intervals(
+        vec![(0u8, 200u8), (254, 255), (255, 255)],
+        IntervalKind::HalfOpen,
+        50,
+        |&x| x,
+    )
+    .map(|i| (i.start, i.end))
+    .collect();
+    assert_eq!(bounds, &[(0, 200), (254, 255)]);
+
+    let input = vec![(i8::MIN, 0i8), (100, i8::MAX)];
+    let bounds: Vec<_> = coalesce_intervals(input.clone(), IntervalKind::Closed, 0, |&x| x)
+        .map(|i| (i.start, i.end))
+        .collect();
+    assert_eq!(bounds, &[(i8::MIN, 0), (100, i8::MAX)]);
+
+    let bounds: Vec<_> = coalesce_intervals(input, IntervalKind::Closed, i8::MAX, |&x| x)
+        .map(|i| (i.start, i.end))
+        .collect();
+    assert_eq!(bounds, &[(i8::MIN, i8::MAX)]);
+
+    let res: Vec<_> = coalesce_ranges(vec![0..10, 20..u8::MAX, u8::MAX..u8::MAX]).collect();
+    assert_eq!(res, &[0..10, 20..u8::MAX]);
+}
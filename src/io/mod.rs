@@ -0,0 +1,45 @@
+use crate::classify_::Classify;
+use std::{
+    io::{self, BufRead, Write},
+    string::String,
+    vec::Vec,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Classify the lines of a reader (without line terminators),
+/// like [`classify`](crate::classify_::classify).
+///
+/// The lines are classified while reading, reading stops at the first error,
+/// which is returned after the classification.
+pub fn classify_lines<R, TC>(
+    reader: R,
+    mut fnx: impl FnMut(&str) -> TC,
+) -> io::Result<Vec<(TC, Vec<String>)>>
+where
+    R: BufRead,
+    TC: Default + PartialEq,
+{
+    let mut err = None;
+    let ret = reader
+        .lines()
+        .map_while(|line| line.map_err(|e| err = Some(e)).ok())
+        .classify(|line: &String| fnx(line))
+        .collect();
+    match err {
+        Some(e) => Err(e),
+        None => Ok(ret),
+    }
+}
+
+/// Write the runs of identical bytes (see [`byte_runs`](crate::byte_runs::byte_runs))
+/// as run-length encoded `(length, byte)` pairs, splitting runs longer than 255 bytes.
+pub fn write_byte_runs<W: Write>(input: &[u8], mut writer: W) -> io::Result<()> {
+    for (byte, run) in crate::byte_runs::byte_runs(input) {
+        for chunk in run.chunks(usize::from(u8::MAX)) {
+            writer.write_all(&[chunk.len() as u8, byte])?;
+        }
+    }
+    Ok(())
+}
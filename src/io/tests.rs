@@ -0,0 +1,35 @@
+use super::*;
+use std::{string::ToString, vec};
+
+#[test]
+fn test_classify_lines() {
+    let input: &[u8] = b"a\nb\n\nc\n";
+    let res = classify_lines(input, |l| l.is_empty()).unwrap();
+    assert_eq!(
+        res,
+        vec![
+            (false, vec!["a".to_string(), "b".to_string()]),
+            (true, vec![String::new()]),
+            (false, vec!["c".to_string()]),
+        ]
+    );
+}
+
+#[test]
+fn test_classify_lines_error() {
+    // invalid UTF-8 in the second line
+    let input: &[u8] = b"a\n\xff\nb\n";
+    let mut seen = Vec::new();
+    let err = classify_lines(input, |l| seen.push(l.to_string())).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(seen, vec!["a".to_string()]);
+}
+
+#[test]
+fn test_write_byte_runs() {
+    let mut input = vec![7u8; 300];
+    input.push(1);
+    let mut out = Vec::new();
+    write_byte_runs(&input, &mut out).unwrap();
+    assert_eq!(out, &[255, 7, 45, 7, 1, 1]);
+}
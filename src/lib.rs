@@ -2,13 +2,48 @@
 #![forbid(unsafe_code)]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+pub mod byte_runs;
 pub mod classify_;
+pub mod declassify;
+pub mod grid;
+pub mod intervals;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod merge_group;
+pub mod partition;
+pub mod run_diff;
+pub mod slice_group;
+#[cfg(test)]
+mod test_util;
+pub mod tokenize;
 pub mod tree_collapse;
+pub mod tree_nav;
 pub mod tree_rollup;
+pub mod tree_walk;
 
 pub use crate::{
-    classify_::{classify, classify_as_vec, Classify},
-    tree_collapse::collapse_tree,
-    tree_rollup::{rollup_tree, rollup_tree_bottomup},
+    byte_runs::byte_runs,
+    classify_::{
+        classify, classify_as_vec, classify_bounded, classify_ext, classify_fuzzy, classify_merge,
+        classify_weighted, ClassMerge, Classify,
+    },
+    declassify::{declassify, declassify_ref, reclassify, reclassify_with},
+    grid::{grid_regions, Connectivity},
+    intervals::{coalesce_intervals, coalesce_ranges},
+    merge_group::merge_group,
+    partition::{stable_partition, stable_partition_ord},
+    run_diff::{diff_runs, spans_of},
+    slice_group::{sort_and_group, sort_unstable_and_group},
+    tokenize::{char_class, classify_bytes, classify_str, CharClass},
+    tree_collapse::{
+        collapse_tree, collapse_tree_dense, collapse_tree_ref, collapse_tree_with_meta,
+    },
+    tree_rollup::{
+        rollup_tree, rollup_tree_bottomup, rollup_tree_bottomup_dense, rollup_tree_breadthfirst,
+        rollup_tree_dense,
+    },
+    tree_walk::{walk_tree_bottomup, walk_tree_breadthfirst, walk_tree_topdown},
 };
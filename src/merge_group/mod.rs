@@ -0,0 +1,151 @@
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::cmp::Reverse;
+
+#[cfg(test)]
+mod tests;
+
+/// Iterator which merges multiple iterators (each sorted by the key function)
+/// and groups adjacent elements with equal keys, like [`ClassifyIT`](crate::classify_::ClassifyIT).
+///
+/// Each element is reported together with the index of the source iterator
+/// it was taken from. Elements with equal keys are ordered by source index,
+/// and stay in their original order within each source.
+#[must_use]
+pub struct MergeGroupIT<TT, K, FnT, IT> {
+    inputs: Vec<IT>,
+    heads: Vec<Option<TT>>,
+    /// min-heap of `(key, source index)` of all non-empty heads
+    queue: BinaryHeap<Reverse<(K, usize)>>,
+    fnx: FnT,
+}
+
+impl<TT, K, FnT, IT> MergeGroupIT<TT, K, FnT, IT>
+where
+    K: Ord,
+    FnT: FnMut(&TT) -> K,
+    IT: Iterator<Item = TT>,
+{
+    pub fn new(inputs: impl IntoIterator<Item = IT>, fnx: FnT) -> Self {
+        let inputs: Vec<IT> = inputs.into_iter().collect();
+        let mut ret = Self {
+            heads: inputs.iter().map(|_| None).collect(),
+            queue: BinaryHeap::with_capacity(inputs.len()),
+            inputs,
+            fnx,
+        };
+        for src in 0..ret.inputs.len() {
+            ret.refill(src);
+        }
+        ret
+    }
+
+    fn refill(&mut self, src: usize) {
+        if let Some(x) = self.inputs[src].next() {
+            self.queue.push(Reverse(((self.fnx)(&x), src)));
+            self.heads[src] = Some(x);
+        }
+    }
+
+    fn pop(&mut self) -> Option<(K, usize, TT)> {
+        let Reverse((key, src)) = self.queue.pop()?;
+        let x = self.heads[src].take().unwrap();
+        self.refill(src);
+        Some((key, src, x))
+    }
+}
+
+impl<TT, K, FnT, IT> Iterator for MergeGroupIT<TT, K, FnT, IT>
+where
+    K: Ord,
+    FnT: FnMut(&TT) -> K,
+    IT: Iterator<Item = TT>,
+{
+    type Item = (K, Vec<(usize, TT)>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, src, x) = self.pop()?;
+        let mut last = vec![(src, x)];
+        while self.queue.peek().is_some_and(|Reverse((k, _))| *k == key) {
+            let (_, src, x) = self.pop().unwrap();
+            last.push((src, x));
+        }
+        Some((key, last))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (mut lower, mut upper) = (self.queue.len(), Some(self.queue.len()));
+        for i in &self.inputs {
+            let (l, u) = i.size_hint();
+            lower = lower.saturating_add(l);
+            upper = upper.and_then(|upper| u.and_then(|u| upper.checked_add(u)));
+        }
+        (usize::from(lower != 0), upper)
+    }
+}
+
+/**
+Merge multiple iterators sorted by `fnx` and group elements with equal keys.
+
+Return value: an iterator of `(key, [(source index, element)])` groups.
+**/
+#[inline]
+pub fn merge_group<Inputs, TT, K, FnT>(
+    inputs: Inputs,
+    fnx: FnT,
+) -> MergeGroupIT<TT, K, FnT, <Inputs::Item as IntoIterator>::IntoIter>
+where
+    Inputs: IntoIterator,
+    Inputs::Item: IntoIterator<Item = TT>,
+    K: Ord,
+    FnT: FnMut(&TT) -> K,
+{
+    MergeGroupIT::new(inputs.into_iter().map(IntoIterator::into_iter), fnx)
+}
+
+/// Distribute the members of a group over one bucket per source
+fn split_by_source<TT>(srccnt: usize, group: Vec<(usize, TT)>) -> Vec<Vec<TT>> {
+    let mut ret: Vec<Vec<TT>> = (0..srccnt).map(|_| Vec::new()).collect();
+    for (src, x) in group {
+        ret[src].push(x);
+    }
+    ret
+}
+
+/**
+Full outer join of multiple iterators sorted by `fnx`.
+
+Return value: an iterator of `(key, buckets)`, where `buckets[i]` contains
+the elements of source `i` with that key (possibly none).
+**/
+pub fn outer_join<Inputs, TT, K, FnT>(
+    inputs: Inputs,
+    fnx: FnT,
+) -> impl Iterator<Item = (K, Vec<Vec<TT>>)>
+where
+    Inputs: IntoIterator,
+    Inputs::Item: IntoIterator<Item = TT>,
+    K: Ord,
+    FnT: FnMut(&TT) -> K,
+{
+    let it = merge_group(inputs, fnx);
+    let srccnt = it.inputs.len();
+    it.map(move |(key, group)| (key, split_by_source(srccnt, group)))
+}
+
+/**
+Inner join of multiple iterators sorted by `fnx`.
+
+Like [`outer_join`], but only keys which are present in every source are yielded.
+**/
+pub fn inner_join<Inputs, TT, K, FnT>(
+    inputs: Inputs,
+    fnx: FnT,
+) -> impl Iterator<Item = (K, Vec<Vec<TT>>)>
+where
+    Inputs: IntoIterator,
+    Inputs::Item: IntoIterator<Item = TT>,
+    K: Ord,
+    FnT: FnMut(&TT) -> K,
+{
+    outer_join(inputs, fnx).filter(|(_, buckets)| buckets.iter().all(|b| !b.is_empty()))
+}
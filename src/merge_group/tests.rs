@@ -0,0 +1,51 @@
+use super::*;
+use alloc::{vec, vec::Vec};
+
+#[test]
+fn test_merge_group() {
+    let inputs = vec![vec![1, 3, 3, 5], vec![0, 3, 6], vec![], vec![1, 5]];
+    let res: Vec<_> = merge_group(inputs, |&x| x).collect();
+    assert_eq!(
+        res,
+        &[
+            (0, vec![(1, 0)]),
+            (1, vec![(0, 1), (3, 1)]),
+            (3, vec![(0, 3), (0, 3), (1, 3)]),
+            (5, vec![(0, 5), (3, 5)]),
+            (6, vec![(1, 6)]),
+        ]
+    );
+}
+
+#[test]
+fn test_merge_group_keyfn() {
+    let inputs = vec![vec![(1, 'a'), (2, 'b')], vec![(1, 'c'), (1, 'd')]];
+    let res: Vec<_> = merge_group(inputs, |&(k, _)| k).collect();
+    assert_eq!(
+        res,
+        &[
+            (1, vec![(0, (1, 'a')), (1, (1, 'c')), (1, (1, 'd'))]),
+            (2, vec![(0, (2, 'b'))]),
+        ]
+    );
+}
+
+#[test]
+fn test_joins() {
+    let inputs = vec![vec![1, 2, 4], vec![2, 3, 4, 4]];
+    let outer: Vec<_> = outer_join(inputs.clone(), |&x| x).collect();
+    assert_eq!(
+        outer,
+        &[
+            (1, vec![vec![1], vec![]]),
+            (2, vec![vec![2], vec![2]]),
+            (3, vec![vec![], vec![3]]),
+            (4, vec![vec![4], vec![4, 4]]),
+        ]
+    );
+    let inner: Vec<_> = inner_join(inputs, |&x| x).collect();
+    assert_eq!(
+        inner,
+        &[(2, vec![vec![2], vec![2]]), (4, vec![vec![4], vec![4, 4]])]
+    );
+}
@@ -0,0 +1,127 @@
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use core::ops::Range;
+
+#[cfg(test)]
+mod tests;
+
+/**
+Reorder a slice so that all elements of each class become contiguous,
+while preserving the relative order of the elements within each class (stable partition).
+
+Classes are laid out in order of their first appearance;
+`fnx` is called exactly once per element.
+This complements [`classify`](crate::classify_::classify), which only finds already-contiguous runs.
+
+Each class is looked up with a linear search over the classes seen so far,
+thus this takes O(n·k) for `k` distinct classes;
+use [`stable_partition_ord`] for many distinct classes.
+
+Return value: each class together with the index range of its region.
+**/
+pub fn stable_partition<T, TC>(
+    input: &mut [T],
+    mut fnx: impl FnMut(&T) -> TC,
+) -> Vec<(TC, Range<usize>)>
+where
+    TC: PartialEq,
+{
+    let mut classes: Vec<(TC, usize)> = Vec::new();
+
+    // class id of each element, and the element count of each class
+    let dest: Vec<usize> = input
+        .iter()
+        .map(|x| {
+            let ccl = fnx(x);
+            match classes.iter().position(|(c, _)| *c == ccl) {
+                Some(id) => {
+                    classes[id].1 += 1;
+                    id
+                }
+                None => {
+                    classes.push((ccl, 1));
+                    classes.len() - 1
+                }
+            }
+        })
+        .collect();
+
+    stable_partition_intern(input, dest, classes)
+}
+
+/**
+Like [`stable_partition`], but looks up the classes in a [`BTreeMap`],
+which takes O(n·log k) for `k` distinct classes.
+
+Return value: each class together with the index range of its region,
+in order of their first appearance.
+**/
+pub fn stable_partition_ord<T, TC>(
+    input: &mut [T],
+    mut fnx: impl FnMut(&T) -> TC,
+) -> Vec<(TC, Range<usize>)>
+where
+    TC: Ord,
+{
+    // class -> class id; element count of each class
+    let mut ids: BTreeMap<TC, usize> = BTreeMap::new();
+    let mut counts: Vec<usize> = Vec::new();
+
+    let dest: Vec<usize> = input
+        .iter()
+        .map(|x| {
+            let next_id = counts.len();
+            let id = *ids.entry(fnx(x)).or_insert(next_id);
+            if id == next_id {
+                counts.push(0);
+            }
+            counts[id] += 1;
+            id
+        })
+        .collect();
+
+    let mut classes: Vec<Option<TC>> = core::iter::repeat_with(|| None)
+        .take(counts.len())
+        .collect();
+    for (ccl, id) in ids {
+        classes[id] = Some(ccl);
+    }
+    let classes = classes.into_iter().flatten().zip(counts).collect();
+
+    stable_partition_intern(input, dest, classes)
+}
+
+/// Move each element to the region of its class,
+/// `dest` contains the class id of each element and is overwritten.
+fn stable_partition_intern<T, TC>(
+    input: &mut [T],
+    mut dest: Vec<usize>,
+    classes: Vec<(TC, usize)>,
+) -> Vec<(TC, Range<usize>)> {
+    // class id -> next free position of that class
+    let mut offsets = vec![0; classes.len()];
+    let mut ret = Vec::with_capacity(classes.len());
+    let mut start = 0;
+    for ((ccl, cnt), offset) in classes.into_iter().zip(offsets.iter_mut()) {
+        *offset = start;
+        ret.push((ccl, start..start + cnt));
+        start += cnt;
+    }
+
+    // element index -> destination index
+    for id in dest.iter_mut() {
+        let pos = offsets[*id];
+        offsets[*id] += 1;
+        *id = pos;
+    }
+
+    // apply the permutation in place by following its cycles
+    for i in 0..input.len() {
+        while dest[i] != i {
+            let j = dest[i];
+            input.swap(i, j);
+            dest.swap(i, j);
+        }
+    }
+
+    ret
+}
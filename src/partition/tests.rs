@@ -0,0 +1,49 @@
+use super::*;
+use alloc::{vec, vec::Vec};
+
+#[test]
+fn test_stable_partition() {
+    let mut input = vec![(1, 'a'), (0, 'b'), (1, 'c'), (2, 'd'), (0, 'e'), (1, 'f')];
+    let bounds = stable_partition(&mut input, |&(k, _)| k);
+    assert_eq!(
+        input,
+        &[(1, 'a'), (1, 'c'), (1, 'f'), (0, 'b'), (0, 'e'), (2, 'd'),]
+    );
+    assert_eq!(bounds, &[(1, 0..3), (0, 3..5), (2, 5..6)]);
+}
+
+#[test]
+fn test_stable_partition_eq_classify() {
+    let mut input: Vec<u32> = (0..200).map(|i| (i * 7919) % 13).collect();
+    let mut expected = input.clone();
+    expected.sort_by_key(|x| input.iter().position(|y| y % 3 == x % 3));
+
+    let bounds = stable_partition(&mut input, |x| x % 3);
+    assert_eq!(input, expected);
+    let runs: Vec<_> = crate::classify_as_vec(input.iter().copied(), |x| x % 3);
+    assert_eq!(runs.len(), bounds.len());
+    for ((ccl, elems), (bccl, range)) in runs.into_iter().zip(bounds) {
+        assert_eq!(ccl, bccl);
+        assert_eq!(elems, &input[range]);
+    }
+}
+
+#[test]
+fn test_stable_partition_empty() {
+    let mut input: [u8; 0] = [];
+    assert!(stable_partition(&mut input, |&x| x).is_empty());
+}
+
+#[test]
+fn test_stable_partition_ord_eq_linear() {
+    let mut input: Vec<u32> = (0..2000).map(|i| (i * 7919) % 331).collect();
+    let mut expected = input.clone();
+    let expected_bounds = stable_partition(&mut expected, |&x| x);
+
+    let bounds = stable_partition_ord(&mut input, |&x| x);
+    assert_eq!(input, expected);
+    assert_eq!(bounds, expected_bounds);
+
+    let mut input: [u8; 0] = [];
+    assert!(stable_partition_ord(&mut input, |&x| x).is_empty());
+}
@@ -0,0 +1,166 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+#[cfg(test)]
+mod tests;
+
+/// A single difference between two segmentations,
+/// all run indices refer to the given span lists
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RunChange {
+    /// an aligned pair of runs covers the same elements, but the class changed
+    ClassChange { old: usize, new: usize },
+    /// the boundary after run `old` (at `from`) moved to the boundary after run `new` (at `to`)
+    BoundaryMove {
+        old: usize,
+        new: usize,
+        from: usize,
+        to: usize,
+    },
+    /// one old run was split into multiple new runs
+    Split { old: usize, new: Range<usize> },
+    /// multiple old runs were merged into one new run
+    Merge { old: Range<usize>, new: usize },
+    /// multiple old runs were replaced by a different number of new runs
+    Realign {
+        old: Range<usize>,
+        new: Range<usize>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunDiff {
+    pub changes: Vec<RunChange>,
+    /// fraction of elements which got the same class in both segmentations
+    pub agreement: f64,
+}
+
+/// Convert the output of [`classify`](crate::classify_::classify) into spans,
+/// which can be compared using [`diff_runs`].
+pub fn spans_of<TC, TT>(groups: &[(TC, Vec<TT>)]) -> Vec<(&TC, Range<usize>)> {
+    let mut start = 0;
+    groups
+        .iter()
+        .map(|(ccl, elems)| {
+            let range = start..start + elems.len();
+            start = range.end;
+            (ccl, range)
+        })
+        .collect()
+}
+
+fn is_contiguous<TC>(spans: &[(TC, Range<usize>)]) -> bool {
+    let mut start = 0;
+    spans.iter().all(|(_, range)| {
+        let ret = range.start == start && range.start < range.end;
+        start = range.end;
+        ret
+    })
+}
+
+fn agreement<TC: PartialEq>(old: &[(TC, Range<usize>)], new: &[(TC, Range<usize>)]) -> f64 {
+    let total = old.last().map_or(0, |(_, range)| range.end);
+    if total == 0 {
+        return 1.0;
+    }
+    let (mut i, mut j, mut agree) = (0, 0, 0);
+    while i < old.len() && j < new.len() {
+        let (ocl, orange) = &old[i];
+        let (ncl, nrange) = &new[j];
+        if ocl == ncl {
+            agree += orange.end.min(nrange.end) - orange.start.max(nrange.start);
+        }
+        if orange.end <= nrange.end {
+            i += 1;
+        }
+        if nrange.end <= orange.end {
+            j += 1;
+        }
+    }
+    agree as f64 / total as f64
+}
+
+/**
+Compare two segmentations of the same underlying sequence.
+
+Both span lists must be contiguous, start at `0`, contain no empty spans,
+and cover the same number of elements.
+Spans are aligned into minimal blocks which share their outer boundaries,
+and each block which differs is reported.
+
+Return value:
+* None: the span lists are invalid or cover sequences of different length.
+* Some(diff): the changes in order of position and the agreement score.
+**/
+pub fn diff_runs<TC: PartialEq>(
+    old: &[(TC, Range<usize>)],
+    new: &[(TC, Range<usize>)],
+) -> Option<RunDiff> {
+    if !is_contiguous(old) || !is_contiguous(new) {
+        return None;
+    }
+    if old.last().map(|x| x.1.end) != new.last().map(|x| x.1.end) {
+        return None;
+    }
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() {
+        // find the smallest block ending at a common boundary
+        let (i0, j0) = (i, j);
+        loop {
+            let (oend, nend) = (old[i].1.end, new[j].1.end);
+            if oend <= nend {
+                i += 1;
+            }
+            if nend <= oend {
+                j += 1;
+            }
+            if oend == nend {
+                break;
+            }
+        }
+
+        match (i - i0, j - j0) {
+            (1, 1) => {
+                if old[i0].0 != new[j0].0 {
+                    changes.push(RunChange::ClassChange { old: i0, new: j0 });
+                }
+            }
+            (1, _) => changes.push(RunChange::Split {
+                old: i0,
+                new: j0..j,
+            }),
+            (_, 1) => changes.push(RunChange::Merge {
+                old: i0..i,
+                new: j0,
+            }),
+            (ocnt, ncnt) if ocnt == ncnt => {
+                for (o, n) in (i0..i).zip(j0..j) {
+                    if o + 1 != i {
+                        changes.push(RunChange::BoundaryMove {
+                            old: o,
+                            new: n,
+                            from: old[o].1.end,
+                            to: new[n].1.end,
+                        });
+                    }
+                    if old[o].0 != new[n].0 {
+                        changes.push(RunChange::ClassChange { old: o, new: n });
+                    }
+                }
+            }
+            _ => changes.push(RunChange::Realign {
+                old: i0..i,
+                new: j0..j,
+            }),
+        }
+    }
+
+    Some(RunDiff {
+        changes,
+        agreement: agreement(old, new),
+    })
+}
@@ -0,0 +1,83 @@
+use super::*;
+use crate::classify_as_vec;
+use alloc::vec;
+
+#[test]
+fn test_diff_runs_identical() {
+    let groups = classify_as_vec(vec![0u8, 0, 1, 1, 2], |&x| x);
+    let spans = spans_of(&groups);
+    assert_eq!(spans, vec![(&0, 0..2), (&1, 2..4), (&2, 4..5)]);
+    let diff = diff_runs(&spans, &spans).unwrap();
+    assert_eq!(diff.changes, vec![]);
+    assert_eq!(diff.agreement, 1.0);
+}
+
+#[test]
+fn test_diff_runs_changes() {
+    let old = vec![
+        ('a', 0..2),
+        ('b', 2..4),
+        ('c', 4..8),
+        ('d', 8..9),
+        ('e', 9..10),
+        ('f', 10..12),
+        ('g', 12..13),
+        ('h', 13..16),
+    ];
+    let new = vec![
+        ('a', 0..3),
+        ('b', 3..4),
+        ('c', 4..6),
+        ('x', 6..8),
+        ('d', 8..10),
+        ('f', 10..12),
+        ('y', 12..14),
+        ('h', 14..15),
+        ('i', 15..16),
+    ];
+    let diff = diff_runs(&old, &new).unwrap();
+    assert_eq!(
+        diff.changes,
+        vec![
+            RunChange::BoundaryMove {
+                old: 0,
+                new: 0,
+                from: 2,
+                to: 3,
+            },
+            RunChange::Split { old: 2, new: 2..4 },
+            RunChange::Merge { old: 3..5, new: 4 },
+            RunChange::Realign {
+                old: 6..8,
+                new: 6..9,
+            },
+        ]
+    );
+    // differing elements: 2, 6, 7, 9, 12, 13, 15
+    assert_eq!(diff.agreement, 9.0 / 16.0);
+}
+
+#[test]
+fn test_diff_runs_class_change() {
+    let old = vec![(0, 0..2), (1, 2..3)];
+    let new = vec![(0, 0..2), (2, 2..3)];
+    let diff = diff_runs(&old, &new).unwrap();
+    assert_eq!(
+        diff.changes,
+        vec![RunChange::ClassChange { old: 1, new: 1 }]
+    );
+    assert_eq!(diff.agreement, 2.0 / 3.0);
+}
+
+#[test]
+fn test_diff_runs_invalid() {
+    assert_eq!(diff_runs(&[(0, 0..2)], &[(0, 0..3)]), None);
+    assert_eq!(diff_runs(&[(0, 0..2), (1, 3..4)], &[(0, 0..4)]), None);
+    assert_eq!(
+        diff_runs::<u8>(&[], &[]),
+        Some(RunDiff {
+            changes: vec![],
+            agreement: 1.0,
+        })
+    );
+}
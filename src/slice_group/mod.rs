@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests;
+
+/// Iterator over runs of elements with equal keys of a mutable slice,
+/// yielding the key and the mutable sub-slice.
+#[derive(Debug)]
+#[must_use]
+pub struct GroupsMutIT<'s, T, FnT> {
+    rest: &'s mut [T],
+    fnx: FnT,
+}
+
+impl<'s, T, K, FnT> Iterator for GroupsMutIT<'s, T, FnT>
+where
+    K: PartialEq,
+    FnT: FnMut(&T) -> K,
+{
+    type Item = (K, &'s mut [T]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let fnx = &mut self.fnx;
+        let (first, tail) = self.rest.split_first()?;
+        let key = fnx(first);
+        let end = tail
+            .iter()
+            .position(|x| fnx(x) != key)
+            .map_or(self.rest.len(), |pos| pos + 1);
+        let (group, rest) = core::mem::take(&mut self.rest).split_at_mut(end);
+        self.rest = rest;
+        Some((key, group))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::from(!self.rest.is_empty()), Some(self.rest.len()))
+    }
+}
+
+impl<T, K, FnT> core::iter::FusedIterator for GroupsMutIT<'_, T, FnT>
+where
+    K: PartialEq,
+    FnT: FnMut(&T) -> K,
+{
+}
+
+/**
+Split a mutable slice into runs of elements with equal keys, without copying.
+This is the slice-specialised variant of [`classify`](crate::classify_::classify).
+**/
+#[inline]
+pub fn group_mut<T, K, FnT>(input: &mut [T], fnx: FnT) -> GroupsMutIT<'_, T, FnT>
+where
+    K: PartialEq,
+    FnT: FnMut(&T) -> K,
+{
+    GroupsMutIT { rest: input, fnx }
+}
+
+/**
+Sort a slice (stable) by key and split it into groups of equal keys.
+
+NOTE: the stable sort ([`slice::sort_by_key`]) allocates a temporary buffer,
+use [`sort_unstable_and_group`] to avoid any allocation.
+**/
+#[inline]
+pub fn sort_and_group<T, K, FnT>(input: &mut [T], mut fnx: FnT) -> GroupsMutIT<'_, T, FnT>
+where
+    K: Ord,
+    FnT: FnMut(&T) -> K,
+{
+    input.sort_by_key(&mut fnx);
+    group_mut(input, fnx)
+}
+
+/// Sort a slice (unstable, in place without allocation) by key
+/// and split it into groups of equal keys.
+#[inline]
+pub fn sort_unstable_and_group<T, K, FnT>(input: &mut [T], mut fnx: FnT) -> GroupsMutIT<'_, T, FnT>
+where
+    K: Ord,
+    FnT: FnMut(&T) -> K,
+{
+    input.sort_unstable_by_key(&mut fnx);
+    group_mut(input, fnx)
+}
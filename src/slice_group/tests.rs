@@ -0,0 +1,38 @@
+use super::*;
+use alloc::{vec, vec::Vec};
+
+#[test]
+fn test_sort_and_group() {
+    let mut input = vec![(3, 'a'), (1, 'b'), (3, 'c'), (2, 'd'), (1, 'e')];
+    let res: Vec<_> = sort_and_group(&mut input, |&(k, _)| k)
+        .map(|(k, group)| (k, group.to_vec()))
+        .collect();
+    assert_eq!(
+        res,
+        &[
+            (1, vec![(1, 'b'), (1, 'e')]),
+            (2, vec![(2, 'd')]),
+            (3, vec![(3, 'a'), (3, 'c')]),
+        ]
+    );
+}
+
+#[test]
+fn test_sort_unstable_and_group_mut() {
+    let mut input = vec![5u8, 1, 5, 2, 1, 5];
+    for (k, group) in sort_unstable_and_group(&mut input, |&x| x) {
+        assert!(group.iter().all(|&x| x == k));
+        group[0] += 10;
+    }
+    assert_eq!(input, &[11, 1, 12, 15, 5, 5]);
+}
+
+#[test]
+fn test_group_mut() {
+    let mut input = vec![0u8, 0, 1, 0];
+    let res: Vec<_> = group_mut(&mut input, |&x| x)
+        .map(|(k, group)| (k, group.len()))
+        .collect();
+    assert_eq!(res, &[(0, 2), (1, 1), (0, 1)]);
+    assert_eq!(group_mut(&mut [0u8; 0], |&x| x).next(), None);
+}
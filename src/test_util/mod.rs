@@ -0,0 +1,91 @@
+use alloc::{vec, vec::Vec};
+
+/// Deterministic xorshift32 generator for pseudo-random test inputs
+#[derive(Clone, Debug)]
+pub(crate) struct XorShift(u32);
+
+impl XorShift {
+    /// `seed` must not be zero
+    #[inline]
+    pub(crate) fn new(seed: u32) -> Self {
+        XorShift(seed)
+    }
+
+    #[inline]
+    pub(crate) fn next_u32(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+
+    /// A value in `0..n`
+    #[inline]
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        self.next_u32() as usize % n
+    }
+}
+
+/// Tree node used by the tree tests
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CplxElement {
+    pub(crate) data: usize,
+    pub(crate) children: Vec<CplxElement>,
+}
+
+impl CplxElement {
+    pub(crate) fn new(data: usize) -> Self {
+        Self {
+            data,
+            children: vec![],
+        }
+    }
+}
+
+impl crate::tree_collapse::Node for CplxElement {
+    type ChildrenIter = alloc::vec::IntoIter<CplxElement>;
+    fn take_children(&mut self) -> Self::ChildrenIter {
+        core::mem::take(&mut self.children).into_iter()
+    }
+}
+
+impl<'a> crate::tree_collapse::RefNode<'a> for CplxElement {
+    type ChildrenIter = core::slice::Iter<'a, CplxElement>;
+    fn children(&'a self) -> Self::ChildrenIter {
+        self.children.iter()
+    }
+}
+
+impl crate::tree_rollup::Node for CplxElement {
+    fn push_child(&mut self, child: Self) {
+        self.children.push(child);
+    }
+}
+
+impl crate::tree_rollup::ReversableNode for CplxElement {
+    fn reverse(&mut self) {
+        self.children.reverse();
+    }
+}
+
+/// The forest `0 (1, 2), 3 (4 (6), 5, 7), 8`
+pub(crate) fn complex_input() -> Vec<CplxElement> {
+    vec![
+        CplxElement {
+            data: 0,
+            children: vec![CplxElement::new(1), CplxElement::new(2)],
+        },
+        CplxElement {
+            data: 3,
+            children: vec![
+                CplxElement {
+                    data: 4,
+                    children: vec![CplxElement::new(6)],
+                },
+                CplxElement::new(5),
+                CplxElement::new(7),
+            ],
+        },
+        CplxElement::new(8),
+    ]
+}
@@ -0,0 +1,263 @@
+#[cfg(test)]
+mod tests;
+
+/// Coarse Unicode character categories,
+/// derived from the Unicode-aware predicates of [`char`].
+///
+/// The classes approximate the major general categories
+/// (`L*`, `M*`, `N*`, `Z*`/white space, `P*`, `S*`, `Cc`) without shipping
+/// the full Unicode tables: punctuation and symbols are recognized in ASCII,
+/// Latin-1 and the commonly used punctuation and symbol blocks,
+/// marks in the combining mark blocks and as variation selectors.
+/// Everything else (unassigned code points, ...) is reported as [`CharClass::Other`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CharClass {
+    Letter,
+    Mark,
+    Number,
+    Whitespace,
+    Punctuation,
+    Symbol,
+    Control,
+    Other,
+}
+
+impl Default for CharClass {
+    #[inline]
+    fn default() -> Self {
+        CharClass::Other
+    }
+}
+
+impl From<char> for CharClass {
+    fn from(c: char) -> Self {
+        // letter numbers (`Nl`, e.g. roman numerals) are also alphabetic
+        if c.is_numeric() {
+            CharClass::Number
+        } else if is_alphabetic_symbol(c) {
+            CharClass::Symbol
+        } else if is_mark(c) {
+            CharClass::Mark
+        } else if c.is_alphabetic() {
+            CharClass::Letter
+        } else if c.is_whitespace() {
+            CharClass::Whitespace
+        } else if c.is_control() {
+            CharClass::Control
+        } else if is_punctuation(c) {
+            CharClass::Punctuation
+        } else if is_symbol(c) {
+            CharClass::Symbol
+        } else {
+            CharClass::Other
+        }
+    }
+}
+
+/// Symbols (`So`) which are alphabetic, e.g. circled and squared letters
+fn is_alphabetic_symbol(c: char) -> bool {
+    matches!(
+        c,
+        '\u{24B6}'..='\u{24E9}'
+            | '\u{1F130}'..='\u{1F149}'
+            | '\u{1F150}'..='\u{1F169}'
+            | '\u{1F170}'..='\u{1F189}'
+    )
+}
+
+/// Combining marks (`M*`) of the combining mark blocks
+/// and variation selectors, which are used in decomposed (NFD) text
+fn is_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{0483}'..='\u{0489}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{3099}' | '\u{309A}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FE20}'..='\u{FE2F}'
+            | '\u{E0100}'..='\u{E01EF}'
+    )
+}
+
+/// Punctuation (`P*`) in ASCII, Latin-1 and the blocks commonly used in text
+fn is_punctuation(c: char) -> bool {
+    matches!(
+        c,
+        '!'..='#' | '%'..='*' | ','..='/' | ':' | ';' | '?' | '@' | '['..=']' | '_' | '{' | '}'
+            | '\u{00A1}' | '\u{00A7}' | '\u{00AB}' | '\u{00B6}' | '\u{00B7}' | '\u{00BB}' | '\u{00BF}'
+            | '\u{2010}'..='\u{2027}'
+            | '\u{2030}'..='\u{2043}'
+            | '\u{2045}'..='\u{2051}'
+            | '\u{2053}'..='\u{205E}'
+            | '\u{2308}'..='\u{230B}'
+            | '\u{2329}' | '\u{232A}'
+            | '\u{2768}'..='\u{2775}'
+            | '\u{27C5}' | '\u{27C6}'
+            | '\u{27E6}'..='\u{27EF}'
+            | '\u{2983}'..='\u{2998}'
+            | '\u{29D8}'..='\u{29DB}'
+            | '\u{29FC}' | '\u{29FD}'
+            | '\u{2E00}'..='\u{2E7F}'
+            | '\u{3001}'..='\u{3003}'
+            | '\u{3008}'..='\u{3011}'
+            | '\u{3014}'..='\u{301F}'
+            | '\u{FF01}'..='\u{FF03}'
+            | '\u{FF05}'..='\u{FF0A}'
+            | '\u{FF0C}'..='\u{FF0F}'
+            | '\u{FF1A}' | '\u{FF1B}' | '\u{FF1F}' | '\u{FF20}'
+            | '\u{FF3B}'..='\u{FF3D}'
+            | '\u{FF3F}' | '\u{FF5B}' | '\u{FF5D}'
+            | '\u{FF5F}'..='\u{FF65}'
+    )
+}
+
+/// Symbols (`Sm`, `Sc`, `Sk`, `So`) in ASCII, Latin-1 and the symbol blocks;
+/// letters, numbers and punctuation inside these blocks must be checked before.
+fn is_symbol(c: char) -> bool {
+    matches!(
+        c,
+        '$' | '+' | '<'..='>' | '^' | '`' | '|' | '~'
+            | '\u{00A2}'..='\u{00A6}'
+            | '\u{00A8}' | '\u{00A9}' | '\u{00AC}'
+            | '\u{00AE}'..='\u{00B1}'
+            | '\u{00B4}' | '\u{00B8}' | '\u{00D7}' | '\u{00F7}'
+            | '\u{02C2}'..='\u{02C5}'
+            | '\u{02D2}'..='\u{02DF}'
+            | '\u{02E5}'..='\u{02EB}'
+            | '\u{02ED}'
+            | '\u{02EF}'..='\u{02FF}'
+            | '\u{0384}' | '\u{0385}'
+            | '\u{2044}' | '\u{2052}'
+            | '\u{207A}'..='\u{207C}'
+            | '\u{208A}'..='\u{208C}'
+            | '\u{20A0}'..='\u{20C0}'
+            | '\u{2100}'..='\u{214F}'
+            | '\u{2190}'..='\u{2BFF}'
+            | '\u{3004}' | '\u{3012}' | '\u{3013}' | '\u{3020}'
+            | '\u{FF04}' | '\u{FF0B}'
+            | '\u{FF1C}'..='\u{FF1E}'
+            | '\u{FF3E}' | '\u{FF40}' | '\u{FF5C}' | '\u{FF5E}'
+            | '\u{FFE0}'..='\u{FFEE}'
+            | '\u{1F000}'..='\u{1FAFF}'
+    )
+}
+
+/// Classify a character by its [`CharClass`],
+/// usable directly as classification function for [`classify_str`].
+#[inline]
+pub fn char_class(c: &char) -> CharClass {
+    CharClass::from(*c)
+}
+
+/// Iterator over runs of equally classified characters of a string,
+/// yielding sub-slices of the input.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct StrClassifyIT<'s, FnT> {
+    rest: &'s str,
+    fnx: FnT,
+}
+
+impl<'s, TC, FnT> Iterator for StrClassifyIT<'s, FnT>
+where
+    TC: PartialEq,
+    FnT: FnMut(&char) -> TC,
+{
+    type Item = (TC, &'s str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chars = self.rest.char_indices();
+        let ccl = (self.fnx)(&chars.next()?.1);
+        let end = chars
+            .find(|(_, c)| (self.fnx)(c) != ccl)
+            .map_or(self.rest.len(), |(idx, _)| idx);
+        let (run, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some((ccl, run))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::from(!self.rest.is_empty()), Some(self.rest.len()))
+    }
+}
+
+impl<TC, FnT> core::iter::FusedIterator for StrClassifyIT<'_, FnT>
+where
+    TC: PartialEq,
+    FnT: FnMut(&char) -> TC,
+{
+}
+
+/// Iterator over runs of equally classified bytes of a byte slice,
+/// yielding sub-slices of the input.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct BytesClassifyIT<'s, FnT> {
+    rest: &'s [u8],
+    fnx: FnT,
+}
+
+impl<'s, TC, FnT> Iterator for BytesClassifyIT<'s, FnT>
+where
+    TC: PartialEq,
+    FnT: FnMut(&u8) -> TC,
+{
+    type Item = (TC, &'s [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first, tail) = self.rest.split_first()?;
+        let ccl = (self.fnx)(first);
+        let end = tail
+            .iter()
+            .position(|x| (self.fnx)(x) != ccl)
+            .map_or(self.rest.len(), |pos| pos + 1);
+        let (run, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some((ccl, run))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::from(!self.rest.is_empty()), Some(self.rest.len()))
+    }
+}
+
+impl<TC, FnT> core::iter::FusedIterator for BytesClassifyIT<'_, FnT>
+where
+    TC: PartialEq,
+    FnT: FnMut(&u8) -> TC,
+{
+}
+
+/**
+Split a string into runs of characters with equal class.
+This is the string-specialised variant of [`classify`](crate::classify_::classify),
+which yields sub-slices (split at character boundaries) instead of collected elements.
+**/
+#[inline]
+pub fn classify_str<TC, FnT>(input: &str, fnx: FnT) -> StrClassifyIT<'_, FnT>
+where
+    TC: PartialEq,
+    FnT: FnMut(&char) -> TC,
+{
+    StrClassifyIT { rest: input, fnx }
+}
+
+/**
+Split a byte slice into runs of bytes with equal class.
+This is the byte-specialised variant of [`classify`](crate::classify_::classify),
+which yields sub-slices instead of collected elements.
+**/
+#[inline]
+pub fn classify_bytes<TC, FnT>(input: &[u8], fnx: FnT) -> BytesClassifyIT<'_, FnT>
+where
+    TC: PartialEq,
+    FnT: FnMut(&u8) -> TC,
+{
+    BytesClassifyIT { rest: input, fnx }
+}
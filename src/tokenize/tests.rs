@@ -0,0 +1,93 @@
+use super::*;
+use crate::classify_as_vec;
+use alloc::{string::String, vec::Vec};
+
+#[test]
+fn test_classify_str() {
+    let res: Vec<_> = classify_str("abc 123, übergrößé!", char_class).collect();
+    assert_eq!(
+        res,
+        &[
+            (CharClass::Letter, "abc"),
+            (CharClass::Whitespace, " "),
+            (CharClass::Number, "123"),
+            (CharClass::Punctuation, ","),
+            (CharClass::Whitespace, " "),
+            (CharClass::Letter, "übergrößé"),
+            (CharClass::Punctuation, "!"),
+        ]
+    );
+}
+
+#[test]
+fn test_char_class_symbols() {
+    for c in "$+<=>^`|~±×÷€¢©°⁄⁒→∑≤╬ⓐⒶ😀＋＄".chars() {
+        assert_eq!(CharClass::from(c), CharClass::Symbol, "{:?}", c);
+    }
+    for c in "!\"#%&'()*,-./:;?@[\\]_{}¡§«»¿–—‘’…‰⁅⁆⟨⟩、「」！（）".chars()
+    {
+        assert_eq!(CharClass::from(c), CharClass::Punctuation, "{:?}", c);
+    }
+    for c in "7½²Ⅻⅻ〇①６".chars() {
+        assert_eq!(CharClass::from(c), CharClass::Number, "{:?}", c);
+    }
+    for c in "aZßñΩжℂ中".chars() {
+        assert_eq!(CharClass::from(c), CharClass::Letter, "{:?}", c);
+    }
+    for c in "⌈⌉⌊⌋〈〉".chars() {
+        assert_eq!(CharClass::from(c), CharClass::Punctuation, "{:?}", c);
+    }
+    for c in "\u{301}\u{308}\u{20DD}\u{FE0F}\u{E0100}".chars() {
+        assert_eq!(CharClass::from(c), CharClass::Mark, "{:?}", c);
+    }
+}
+
+#[test]
+fn test_classify_str_nfd() {
+    // decomposed "café", "ñ" and an emoji with variation selector
+    let res: Vec<_> = classify_str("cafe\u{301} n\u{303}\u{2764}\u{FE0F}", char_class).collect();
+    assert_eq!(
+        res,
+        &[
+            (CharClass::Letter, "cafe"),
+            (CharClass::Mark, "\u{301}"),
+            (CharClass::Whitespace, " "),
+            (CharClass::Letter, "n"),
+            (CharClass::Mark, "\u{303}"),
+            (CharClass::Symbol, "\u{2764}"),
+            (CharClass::Mark, "\u{FE0F}"),
+        ]
+    );
+}
+
+#[test]
+fn test_classify_str_eq_classify() {
+    let input = "Grüße — 2×3\t= ６!\n";
+    let expected: Vec<_> = classify_as_vec(input.chars(), char_class)
+        .into_iter()
+        .map(|(ccl, chars)| (ccl, chars.into_iter().collect::<String>()))
+        .collect();
+    let res: Vec<_> = classify_str(input, char_class)
+        .map(|(ccl, run)| (ccl, String::from(run)))
+        .collect();
+    assert_eq!(res, expected);
+    assert_eq!(classify_str("", char_class).next(), None);
+}
+
+#[test]
+fn test_classify_bytes() {
+    let input: &[u8] = &[0, 0, 1, 1, 2, 2, 3, 0, 5, 5, 5];
+    let res: Vec<_> = classify_bytes(input, |&x| x).collect();
+    assert_eq!(
+        res,
+        &[
+            (0, &[0, 0][..]),
+            (1, &[1, 1][..]),
+            (2, &[2, 2][..]),
+            (3, &[3][..]),
+            (0, &[0][..]),
+            (5, &[5, 5, 5][..]),
+        ]
+    );
+    assert_eq!(classify_bytes(&[], |&x| x).next(), None);
+}
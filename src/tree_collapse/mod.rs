@@ -1,4 +1,7 @@
-use alloc::{collections::BTreeMap, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    vec::Vec,
+};
 
 #[cfg(test)]
 mod tests;
@@ -8,80 +11,472 @@ pub trait Node: Sized {
     fn take_children(&mut self) -> Self::ChildrenIter;
 }
 
+/// Like [`Node`], but the children are only borrowed,
+/// used by [`collapse_tree_ref`] to flatten a tree without dismantling it.
+pub trait RefNode<'a>: 'a {
+    type ChildrenIter: Iterator<Item = &'a Self>;
+    fn children(&'a self) -> Self::ChildrenIter;
+}
+
+/// Adapter which makes a borrowed [`RefNode`] usable as [`Node`]
+struct RefNodeWrap<'a, T>(&'a T);
+
+impl<'a, T: RefNode<'a>> Node for RefNodeWrap<'a, T> {
+    type ChildrenIter = core::iter::Map<T::ChildrenIter, fn(&'a T) -> Self>;
+    #[inline]
+    fn take_children(&mut self) -> Self::ChildrenIter {
+        self.0.children().map(RefNodeWrap)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CollapsedTree<T> {
     pub elems: Vec<T>,
     /// mapping from child to parent index
+    #[cfg_attr(feature = "serde", serde(with = "mapping_as_pairs"))]
     pub mapping: BTreeMap<usize, usize>,
+    /// layout of `elems`
+    pub order: CollapseTreeOrd,
+}
+
+/// Like [`CollapsedTree`], but with a dense parent array instead of a mapping
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DenseCollapsedTree<T> {
+    pub elems: Vec<T>,
+    /// parent index of each element, `None` for top-level elements
+    pub parents: Vec<Option<usize>>,
+    /// layout of `elems`
+    pub order: CollapseTreeOrd,
+}
+
+impl<T> DenseCollapsedTree<T> {
+    /// Iterate over all (child, parent) pairs, sorted by child,
+    /// usable as `mapping` argument of the [`tree_rollup`](crate::tree_rollup) functions.
+    #[inline]
+    pub fn mapping_iter(&self) -> impl DoubleEndedIterator<Item = (usize, usize)> + '_ {
+        parent_pairs(&self.parents)
+    }
+}
+
+/// Iterate over all (child, parent) pairs of a dense parent array, sorted by child.
+#[inline]
+pub fn parent_pairs(
+    parents: &[Option<usize>],
+) -> impl DoubleEndedIterator<Item = (usize, usize)> + '_ {
+    parents
+        .iter()
+        .enumerate()
+        .filter_map(|(child, parent)| parent.map(|parent| (child, parent)))
+}
+
+impl<T> core::convert::TryFrom<CollapsedTree<T>> for DenseCollapsedTree<T> {
+    type Error = InvalidMapping;
+
+    /// The `mapping` is checked using [`validate`](CollapsedTree::validate).
+    fn try_from(x: CollapsedTree<T>) -> Result<Self, InvalidMapping> {
+        x.validate()?;
+        let mut parents = alloc::vec![None; x.elems.len()];
+        for (child, parent) in x.mapping {
+            parents[child] = Some(parent);
+        }
+        Ok(Self {
+            elems: x.elems,
+            parents,
+            order: x.order,
+        })
+    }
+}
+
+impl<T> From<DenseCollapsedTree<T>> for CollapsedTree<T> {
+    fn from(x: DenseCollapsedTree<T>) -> Self {
+        Self {
+            mapping: parent_pairs(&x.parents).collect(),
+            elems: x.elems,
+            order: x.order,
+        }
+    }
+}
+
+/// (De-)serialize the mapping as a compact sequence of `(child, parent)` pairs,
+/// instead of a map (which would use string keys in e.g. JSON)
+#[cfg(feature = "serde")]
+mod mapping_as_pairs {
+    use alloc::{collections::BTreeMap, vec::Vec};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        mapping: &BTreeMap<usize, usize>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(mapping.iter())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<usize, usize>, D::Error> {
+        Ok(Vec::<(usize, usize)>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
 }
 
+/// An entry of a child-to-parent mapping which doesn't describe a valid tree
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidMapping {
+    pub child: usize,
+    pub parent: usize,
+}
+
+impl core::fmt::Display for InvalidMapping {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "invalid tree mapping entry (child {} -> parent {})",
+            self.child, self.parent
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidMapping {}
+
+impl<T> CollapsedTree<T> {
+    /// Check that all `mapping` entries refer to elements
+    /// and that the elements are laid out as described by `order`
+    /// (which also rules out elements being their own ancestor).
+    pub fn validate(&self) -> Result<(), InvalidMapping> {
+        let len = self.elems.len();
+        if let Some((&child, &parent)) = self
+            .mapping
+            .iter()
+            .find(|&(&child, &parent)| child >= len || parent >= len)
+        {
+            return Err(InvalidMapping { child, parent });
+        }
+
+        match self.order {
+            CollapseTreeOrd::TopDown => self.validate_path(0..len),
+            // reversed post-order is a pre-order with reversed siblings
+            CollapseTreeOrd::BottomUp => self.validate_path((0..len).rev()),
+            CollapseTreeOrd::BreadthFirst => {
+                // all top-level elements come first, then the children
+                // grouped by parent, in order of their parents
+                let mut last: Option<(usize, usize)> = None;
+                for i in 0..len {
+                    match (self.mapping.get(&i), last) {
+                        (None, None) => {}
+                        (None, Some((child, parent))) => {
+                            return Err(InvalidMapping { child, parent })
+                        }
+                        (Some(&parent), _)
+                            if parent >= i || last.is_some_and(|(_, lp)| parent < lp) =>
+                        {
+                            return Err(InvalidMapping { child: i, parent })
+                        }
+                        (Some(&parent), _) => last = Some((i, parent)),
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Check that the parent of each element (in walk order)
+    /// is on the path from the top-level element to the previous element.
+    fn validate_path(&self, walk: impl Iterator<Item = usize>) -> Result<(), InvalidMapping> {
+        let mut path = Vec::new();
+        for i in walk {
+            match self.mapping.get(&i) {
+                None => path.clear(),
+                Some(&parent) => {
+                    while path.last().is_some_and(|&top| top != parent) {
+                        path.pop();
+                    }
+                    if path.is_empty() {
+                        return Err(InvalidMapping { child: i, parent });
+                    }
+                }
+            }
+            path.push(i);
+        }
+        Ok(())
+    }
+
+    /// Copy the mapping into a [`HashMap`](std::collections::HashMap),
+    /// for O(1) parent lookups.
+    #[cfg(feature = "std")]
+    pub fn hash_mapping(&self) -> std::collections::HashMap<usize, usize> {
+        self.mapping.iter().map(|(&c, &p)| (c, p)).collect()
+    }
+
+    /// Build a collapsed tree from elements and a [`HashMap`](std::collections::HashMap)-based mapping,
+    /// checked using [`validate`](CollapsedTree::validate).
+    #[cfg(feature = "std")]
+    pub fn try_from_hash_mapping<S>(
+        elems: Vec<T>,
+        mapping: std::collections::HashMap<usize, usize, S>,
+        order: CollapseTreeOrd,
+    ) -> Result<Self, InvalidMapping> {
+        let ret = Self {
+            elems,
+            mapping: mapping.into_iter().collect(),
+            order,
+        };
+        ret.validate()?;
+        Ok(ret)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CollapseTreeOrd {
+    /// pre-order: each parent is followed by its subtrees
     TopDown,
+    /// post-order: each parent follows its subtrees
     BottomUp,
+    /// level-order: all roots, then all their children, and so on
+    BreadthFirst,
 }
 
 /**
 Collapse a tree into a flat structure and a mapping.
 This is the opposite function of [`rollup_tree`](crate::tree_rollup::rollup_tree).
+
+The tree is walked using an explicit stack instead of recursion,
+thus arbitrarily deep trees are supported, and every element is moved only once.
 **/
 pub fn collapse_tree<T, InIter>(input: InIter, order: CollapseTreeOrd) -> CollapsedTree<T>
 where
     T: Node,
     InIter: Iterator<Item = T>,
 {
-    let mut ret: CollapsedTree<T> = CollapsedTree {
-        elems: Vec::with_capacity({
-            let s_h = input.size_hint();
-            s_h.1.unwrap_or(s_h.0)
-        }),
-        mapping: BTreeMap::new(),
-    };
-    let is_topdown = order == CollapseTreeOrd::TopDown;
-
-    for mut i in input {
-        // recursion
-        let CollapsedTree {
-            elems: subs_elems,
-            mapping: subs_mapping,
-        } = collapse_tree(i.take_children(), order);
-
-        let mut i = Some(i);
-        if is_topdown {
-            // push current element
-            ret.elems.push(i.take().unwrap());
-        }
+    let (elems, mapping) = collapse_tree_intern(input, order);
+    CollapsedTree {
+        elems,
+        mapping: mapping.into_iter().collect(),
+        order,
+    }
+}
 
-        // element positions for c2p mapping
-        let (subs_cnt, subs_start_id) = (subs_elems.len(), ret.elems.len());
+/**
+Collapse a tree into a flat structure and a dense parent array.
+See also [`collapse_tree`].
+**/
+pub fn collapse_tree_dense<T, InIter>(
+    input: InIter,
+    order: CollapseTreeOrd,
+) -> DenseCollapsedTree<T>
+where
+    T: Node,
+    InIter: Iterator<Item = T>,
+{
+    let (elems, mapping) = collapse_tree_intern(input, order);
+    let mut parents = alloc::vec![None; elems.len()];
+    for (child, parent) in mapping {
+        parents[child] = Some(parent);
+    }
+    DenseCollapsedTree {
+        elems,
+        parents,
+        order,
+    }
+}
 
-        if subs_cnt != 0 {
-            // insert sub elements
-            ret.elems.extend(subs_elems.into_iter());
+/**
+Collapse a borrowed tree into a flat structure of references and a mapping,
+leaving the tree intact. See also [`collapse_tree`].
+**/
+pub fn collapse_tree_ref<'a, T, InIter>(
+    input: InIter,
+    order: CollapseTreeOrd,
+) -> CollapsedTree<&'a T>
+where
+    T: RefNode<'a>,
+    InIter: IntoIterator<Item = &'a T>,
+{
+    let (elems, mapping) = collapse_tree_intern(input.into_iter().map(RefNodeWrap), order);
+    CollapsedTree {
+        elems: elems.into_iter().map(|RefNodeWrap(i)| i).collect(),
+        mapping: mapping.into_iter().collect(),
+        order,
+    }
+}
+
+/// Per-element metadata of a collapsed tree, as parallel arrays indexed like `elems`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreeMeta {
+    /// distance from the top level (top-level elements have depth 0)
+    pub depth: Vec<usize>,
+    /// position among the children of the parent (or among the top-level elements)
+    pub sibling_index: Vec<usize>,
+    /// number of direct children
+    pub child_count: Vec<usize>,
+    /// number of elements in the subtree, including the element itself;
+    /// for `TopDown` the subtree of `i` is `i..i + size`,
+    /// for `BottomUp` it is `i + 1 - size..i + 1`
+    pub subtree_size: Vec<usize>,
+}
+
+impl TreeMeta {
+    /// Compute the metadata from a dense parent array,
+    /// which must be laid out according to `order`.
+    pub fn from_parents(parents: &[Option<usize>], order: CollapseTreeOrd) -> Self {
+        let len = parents.len();
+        let mut ret = TreeMeta {
+            depth: alloc::vec![0; len],
+            sibling_index: alloc::vec![0; len],
+            child_count: alloc::vec![0; len],
+            subtree_size: alloc::vec![1; len],
+        };
 
-            // update c2p mapping
-            let cur_id = if is_topdown {
-                subs_start_id - 1
-            } else {
-                ret.elems.len()
+        // siblings are always laid out in order
+        let mut toplevel_cnt = 0;
+        for (i, parent) in parents.iter().enumerate() {
+            let cnt = match parent {
+                Some(parent) => &mut ret.child_count[*parent],
+                None => &mut toplevel_cnt,
             };
-            for i in 0..subs_cnt {
-                ret.mapping.insert(
-                    i + subs_start_id,
-                    subs_mapping
-                        .get(&i)
-                        .map(|parent| *parent + subs_start_id)
-                        .unwrap_or(cur_id),
-                );
+            ret.sibling_index[i] = *cnt;
+            *cnt += 1;
+        }
+
+        // visit parents before their children
+        let parents_first = |i: usize| match order {
+            CollapseTreeOrd::BottomUp => len - 1 - i,
+            CollapseTreeOrd::TopDown | CollapseTreeOrd::BreadthFirst => i,
+        };
+        for i in (0..len).map(parents_first) {
+            if let Some(parent) = parents[i] {
+                ret.depth[i] = ret.depth[parent] + 1;
+            }
+        }
+        for i in (0..len).rev().map(parents_first) {
+            if let Some(parent) = parents[i] {
+                ret.subtree_size[parent] += ret.subtree_size[i];
             }
         }
 
-        if !is_topdown {
-            // push current element
-            ret.elems.push(i.take().unwrap());
+        ret
+    }
+}
+
+/**
+Like [`collapse_tree`], but additionally records
+the depth, sibling index, child count and subtree size of every element.
+**/
+pub fn collapse_tree_with_meta<T, InIter>(
+    input: InIter,
+    order: CollapseTreeOrd,
+) -> (CollapsedTree<T>, TreeMeta)
+where
+    T: Node,
+    InIter: Iterator<Item = T>,
+{
+    let DenseCollapsedTree {
+        elems,
+        parents,
+        order,
+    } = collapse_tree_dense(input, order);
+    let meta = TreeMeta::from_parents(&parents, order);
+    let tree = CollapsedTree {
+        elems,
+        mapping: parent_pairs(&parents).collect(),
+        order,
+    };
+    (tree, meta)
+}
+
+/// Returns the elements and the unsorted (child, parent) pairs
+fn collapse_tree_intern<T, InIter>(
+    input: InIter,
+    order: CollapseTreeOrd,
+) -> (Vec<T>, Vec<(usize, usize)>)
+where
+    T: Node,
+    InIter: Iterator<Item = T>,
+{
+    let mut elems = Vec::with_capacity({
+        let s_h = input.size_hint();
+        s_h.1.unwrap_or(s_h.0)
+    });
+    let mut mapping: Vec<(usize, usize)> = Vec::new();
+
+    match order {
+        CollapseTreeOrd::TopDown => {
+            // (children of parent, parent id)
+            let mut stack: Vec<(T::ChildrenIter, usize)> = Vec::new();
+            for mut i in input {
+                stack.push((i.take_children(), elems.len()));
+                elems.push(i);
+
+                while let Some((children, parent_id)) = stack.last_mut() {
+                    let parent_id = *parent_id;
+                    match children.next() {
+                        Some(mut i) => {
+                            let cur_id = elems.len();
+                            mapping.push((cur_id, parent_id));
+                            stack.push((i.take_children(), cur_id));
+                            elems.push(i);
+                        }
+                        None => {
+                            stack.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        CollapseTreeOrd::BottomUp => {
+            // (parent, children of parent, start of its finished children in `finished`)
+            let mut stack: Vec<(T, T::ChildrenIter, usize)> = Vec::new();
+            // ids of finished elements whose parent isn't finished yet
+            let mut finished: Vec<usize> = Vec::new();
+            for mut i in input {
+                let children = i.take_children();
+                stack.push((i, children, 0));
+
+                while let Some((_, children, _)) = stack.last_mut() {
+                    match children.next() {
+                        Some(mut i) => {
+                            let children = i.take_children();
+                            stack.push((i, children, finished.len()));
+                        }
+                        None => {
+                            let (i, _, subs_start) = stack.pop().unwrap();
+                            let cur_id = elems.len();
+                            elems.push(i);
+                            mapping.extend(finished.drain(subs_start..).map(|sub| (sub, cur_id)));
+                            if !stack.is_empty() {
+                                finished.push(cur_id);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        CollapseTreeOrd::BreadthFirst => {
+            // (children of parent, parent id), in order of the parent ids
+            let mut queue: VecDeque<(T::ChildrenIter, usize)> = VecDeque::new();
+            for mut i in input {
+                queue.push_back((i.take_children(), elems.len()));
+                elems.push(i);
+            }
+
+            while let Some((children, parent_id)) = queue.pop_front() {
+                for mut i in children {
+                    let cur_id = elems.len();
+                    mapping.push((cur_id, parent_id));
+                    queue.push_back((i.take_children(), cur_id));
+                    elems.push(i);
+                }
+            }
         }
     }
 
-    ret.elems.shrink_to_fit();
-    ret
+    elems.shrink_to_fit();
+    (elems, mapping)
 }
@@ -1,27 +1,7 @@
 use super::*;
+use crate::test_util::{complex_input, CplxElement, XorShift};
 use alloc::{vec, vec::Vec};
-
-#[derive(Debug, PartialEq, Eq)]
-struct CplxElement {
-    data: usize,
-    children: Vec<CplxElement>,
-}
-
-impl CplxElement {
-    fn new(data: usize) -> Self {
-        Self {
-            data,
-            children: vec![],
-        }
-    }
-}
-
-impl Node for CplxElement {
-    type ChildrenIter = alloc::vec::IntoIter<CplxElement>;
-    fn take_children(&mut self) -> Self::ChildrenIter {
-        core::mem::replace(&mut self.children, vec![]).into_iter()
-    }
-}
+use core::convert::TryFrom;
 
 #[test]
 fn test_collapse_complex_tree() {
@@ -59,9 +39,10 @@ fn test_collapse_complex_tree() {
                 CplxElement::new(8)
             ],
             mapping: [(1, 0), (2, 0), (4, 3), (5, 4), (6, 3), (7, 3)]
-                .into_iter()
+                .iter()
                 .copied()
                 .collect(),
+            order: CollapseTreeOrd::TopDown,
         }
     );
 }
@@ -102,9 +83,353 @@ fn test_collapse_bottomup_complex_tree() {
                 CplxElement::new(8)
             ],
             mapping: [(0, 2), (1, 2), (3, 4), (4, 7), (5, 7), (6, 7)]
-                .into_iter()
+                .iter()
+                .copied()
+                .collect(),
+            order: CollapseTreeOrd::BottomUp,
+        }
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_collapsed_tree_serde() {
+    let tree = CollapsedTree {
+        elems: vec![0u8, 1, 2, 3],
+        mapping: [(1, 0), (2, 0), (3, 2)].iter().copied().collect(),
+        order: CollapseTreeOrd::TopDown,
+    };
+    let json = serde_json::to_string(&tree).unwrap();
+    assert_eq!(
+        json,
+        r#"{"elems":[0,1,2,3],"mapping":[[1,0],[2,0],[3,2]],"order":"TopDown"}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<CollapsedTree<u8>>(&json).unwrap(),
+        tree
+    );
+
+    let ord = CollapseTreeOrd::BottomUp;
+    let json = serde_json::to_string(&ord).unwrap();
+    assert_eq!(serde_json::from_str::<CollapseTreeOrd>(&json).unwrap(), ord);
+}
+
+#[test]
+fn test_collapsed_tree_validate() {
+    let mut tree = CollapsedTree {
+        elems: vec![0u8, 1, 2],
+        mapping: [(1, 0), (2, 0)].iter().copied().collect(),
+        order: CollapseTreeOrd::TopDown,
+    };
+    assert_eq!(tree.validate(), Ok(()));
+    tree.mapping.insert(2, 3);
+    assert_eq!(
+        tree.validate(),
+        Err(InvalidMapping {
+            child: 2,
+            parent: 3
+        })
+    );
+
+    // cycle which isn't reachable from a top-level element
+    tree.mapping = [(1, 2), (2, 1)].iter().copied().collect();
+    assert_eq!(
+        tree.validate(),
+        Err(InvalidMapping {
+            child: 1,
+            parent: 2
+        })
+    );
+    // longer cycle, entered from a valid branch
+    tree.elems.extend_from_slice(&[3, 4]);
+    tree.mapping = [(0, 4), (1, 0), (2, 1), (3, 2), (4, 3)]
+        .iter()
+        .copied()
+        .collect();
+    assert!(tree.validate().is_err());
+    tree.mapping.remove(&0);
+    assert_eq!(tree.validate(), Ok(()));
+}
+
+#[test]
+fn test_collapsed_tree_validate_order() {
+    // top-down mapping, but marked as bottom-up
+    let tree = CollapsedTree {
+        elems: vec![0u8, 1, 2],
+        mapping: [(1, 0), (2, 0)].iter().copied().collect(),
+        order: CollapseTreeOrd::BottomUp,
+    };
+    assert_eq!(
+        tree.validate(),
+        Err(InvalidMapping {
+            child: 2,
+            parent: 0
+        })
+    );
+
+    // parent before child, but the subtree of 3 isn't contiguous
+    let tree = CollapsedTree {
+        elems: vec![0u8, 1, 2, 3],
+        mapping: [(1, 0), (2, 3)].iter().copied().collect(),
+        order: CollapseTreeOrd::TopDown,
+    };
+    assert!(tree.validate().is_err());
+
+    let orders = [
+        CollapseTreeOrd::TopDown,
+        CollapseTreeOrd::BottomUp,
+        CollapseTreeOrd::BreadthFirst,
+    ];
+    for &order in &orders {
+        let mut tree = collapse_tree(random_forest(50, 0x5eed).into_iter(), order);
+        assert_eq!(tree.validate(), Ok(()), "order = {:?}", order);
+        for &other in orders.iter().filter(|&&other| other != order) {
+            tree.order = other;
+            assert!(tree.validate().is_err(), "{:?} as {:?}", order, other);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_collapsed_tree_hash_mapping() {
+    let tree = CollapsedTree {
+        elems: vec![0u8, 1, 2],
+        mapping: [(1, 0), (2, 1)].iter().copied().collect(),
+        order: CollapseTreeOrd::TopDown,
+    };
+    let hm = tree.hash_mapping();
+    assert_eq!(hm.get(&2), Some(&1));
+    assert_eq!(
+        CollapsedTree::try_from_hash_mapping(tree.elems.clone(), hm, CollapseTreeOrd::TopDown),
+        Ok(tree)
+    );
+
+    let mut hm = std::collections::HashMap::new();
+    hm.insert(1, 1);
+    let err = CollapsedTree::try_from_hash_mapping(vec![0u8, 1], hm, CollapseTreeOrd::TopDown)
+        .unwrap_err();
+    let err: &dyn std::error::Error = &err;
+    assert_eq!(
+        std::string::ToString::to_string(err),
+        "invalid tree mapping entry (child 1 -> parent 1)"
+    );
+}
+
+#[test]
+fn test_collapse_deep_tree() {
+    const DEPTH: usize = 200_000;
+    // built iteratively; the tree is taken apart by `collapse_tree`,
+    // thus it is never dropped recursively
+    let deep_tree = || {
+        let mut root = CplxElement::new(DEPTH - 1);
+        for data in (0..DEPTH - 1).rev() {
+            root = CplxElement {
+                data,
+                children: vec![root],
+            };
+        }
+        root
+    };
+
+    let res = collapse_tree(core::iter::once(deep_tree()), CollapseTreeOrd::TopDown);
+    assert!(res.elems.iter().enumerate().all(|(i, elem)| elem.data == i));
+    assert!(res
+        .mapping
+        .iter()
+        .all(|(&child, &parent)| parent + 1 == child));
+    assert_eq!(res.mapping.len(), DEPTH - 1);
+
+    let res = collapse_tree(core::iter::once(deep_tree()), CollapseTreeOrd::BottomUp);
+    assert!(res
+        .elems
+        .iter()
+        .enumerate()
+        .all(|(i, elem)| elem.data == DEPTH - 1 - i));
+    assert!(res
+        .mapping
+        .iter()
+        .all(|(&child, &parent)| child + 1 == parent));
+    assert_eq!(res.mapping.len(), DEPTH - 1);
+}
+
+#[test]
+fn test_collapse_breadthfirst_complex_tree() {
+    let input = complex_input();
+
+    assert_eq!(
+        collapse_tree(input.into_iter(), CollapseTreeOrd::BreadthFirst),
+        CollapsedTree {
+            elems: vec![
+                CplxElement::new(0),
+                CplxElement::new(3),
+                CplxElement::new(8),
+                CplxElement::new(1),
+                CplxElement::new(2),
+                CplxElement::new(4),
+                CplxElement::new(5),
+                CplxElement::new(7),
+                CplxElement::new(6)
+            ],
+            mapping: [(3, 0), (4, 0), (5, 1), (6, 1), (7, 1), (8, 5)]
+                .iter()
                 .copied()
                 .collect(),
+            order: CollapseTreeOrd::BreadthFirst,
+        }
+    );
+}
+
+#[test]
+fn test_collapse_dense_complex_tree() {
+    let input = complex_input();
+
+    let res = collapse_tree_dense(input.into_iter(), CollapseTreeOrd::TopDown);
+    assert_eq!(
+        res.parents,
+        vec![
+            None,
+            Some(0),
+            Some(0),
+            None,
+            Some(3),
+            Some(4),
+            Some(3),
+            Some(3),
+            None
+        ]
+    );
+    assert_eq!(
+        res.mapping_iter().collect::<Vec<_>>(),
+        vec![(1, 0), (2, 0), (4, 3), (5, 4), (6, 3), (7, 3)]
+    );
+
+    let sparse = CollapsedTree::from(res.clone());
+    assert_eq!(
+        sparse.mapping,
+        [(1, 0), (2, 0), (4, 3), (5, 4), (6, 3), (7, 3)]
+            .iter()
+            .copied()
+            .collect()
+    );
+    assert_eq!(DenseCollapsedTree::try_from(sparse), Ok(res));
+
+    // children outside of `elems` are rejected instead of extending `parents`
+    let sparse = CollapsedTree {
+        elems: vec![0u8, 1],
+        mapping: [(1, 0), (2, 0)].iter().copied().collect(),
+        order: CollapseTreeOrd::TopDown,
+    };
+    assert_eq!(
+        DenseCollapsedTree::try_from(sparse),
+        Err(InvalidMapping {
+            child: 2,
+            parent: 0
+        })
+    );
+}
+
+#[test]
+fn test_collapse_ref_complex_tree() {
+    let input = complex_input();
+
+    for &order in &[
+        CollapseTreeOrd::TopDown,
+        CollapseTreeOrd::BottomUp,
+        CollapseTreeOrd::BreadthFirst,
+    ] {
+        let res = collapse_tree_ref(&input, order);
+        let owned = collapse_tree(input.clone().into_iter(), order);
+        assert_eq!(res.mapping, owned.mapping);
+        let data: Vec<_> = res.elems.iter().map(|i| i.data).collect();
+        let owned_data: Vec<_> = owned.elems.iter().map(|i| i.data).collect();
+        assert_eq!(data, owned_data);
+    }
+
+    // the input is still intact
+    assert_eq!(input[1].children[0].children, vec![CplxElement::new(6)]);
+}
+
+#[test]
+fn test_collapse_with_meta() {
+    let input = complex_input();
+
+    let (tree, meta) = collapse_tree_with_meta(input.clone().into_iter(), CollapseTreeOrd::TopDown);
+    assert_eq!(
+        tree,
+        collapse_tree(input.clone().into_iter(), CollapseTreeOrd::TopDown)
+    );
+    // elems: 0 1 2 3 4 6 5 7 8
+    assert_eq!(
+        meta,
+        TreeMeta {
+            depth: vec![0, 1, 1, 0, 1, 2, 1, 1, 0],
+            sibling_index: vec![0, 0, 1, 1, 0, 0, 1, 2, 2],
+            child_count: vec![2, 0, 0, 3, 1, 0, 0, 0, 0],
+            subtree_size: vec![3, 1, 1, 5, 2, 1, 1, 1, 1],
         }
     );
+
+    // elems: 1 2 0 6 4 5 7 3 8
+    let (_, meta) = collapse_tree_with_meta(input.clone().into_iter(), CollapseTreeOrd::BottomUp);
+    assert_eq!(
+        meta,
+        TreeMeta {
+            depth: vec![1, 1, 0, 2, 1, 1, 1, 0, 0],
+            sibling_index: vec![0, 1, 0, 0, 0, 1, 2, 1, 2],
+            child_count: vec![0, 0, 2, 0, 1, 0, 0, 3, 0],
+            subtree_size: vec![1, 1, 3, 1, 2, 1, 1, 5, 1],
+        }
+    );
+
+    // elems: 0 3 8 1 2 4 5 7 6
+    let (_, meta) = collapse_tree_with_meta(input.into_iter(), CollapseTreeOrd::BreadthFirst);
+    assert_eq!(
+        meta,
+        TreeMeta {
+            depth: vec![0, 0, 0, 1, 1, 1, 1, 1, 2],
+            sibling_index: vec![0, 1, 2, 0, 1, 0, 1, 2, 0],
+            child_count: vec![2, 3, 0, 0, 0, 1, 0, 0, 0],
+            subtree_size: vec![3, 5, 1, 1, 1, 2, 1, 1, 1],
+        }
+    );
+}
+
+/// Build a pseudo-random forest with `len` elements
+fn random_forest(len: usize, seed: u32) -> Vec<CplxElement> {
+    let mut rng = XorShift::new(seed);
+
+    // insert each new element at a random path
+    let mut ret: Vec<CplxElement> = Vec::new();
+    for data in 0..len {
+        let mut cur = &mut ret;
+        while !cur.is_empty() && rng.below(3) != 0 {
+            let pos = rng.below(cur.len());
+            cur = &mut cur[pos].children;
+        }
+        let pos = rng.below(cur.len() + 1);
+        cur.insert(pos, CplxElement::new(data));
+    }
+    ret
+}
+
+#[test]
+fn test_collapse_rollup_roundtrip() {
+    for len in 0..40 {
+        let input = random_forest(len, 0x1234_5678 + len as u32);
+        for &order in &[
+            CollapseTreeOrd::TopDown,
+            CollapseTreeOrd::BottomUp,
+            CollapseTreeOrd::BreadthFirst,
+        ] {
+            let tree = collapse_tree(input.clone().into_iter(), order);
+            assert_eq!(tree.order, order);
+            assert_eq!(tree.rollup(), Some(input.clone()), "order = {:?}", order);
+
+            let mut tree = collapse_tree(input.clone().into_iter(), order);
+            tree.reorder(CollapseTreeOrd::BottomUp)
+                .expect("valid mapping");
+            assert_eq!(tree.rollup(), Some(input.clone()), "order = {:?}", order);
+        }
+    }
 }
@@ -0,0 +1,348 @@
+use crate::tree_collapse::{CollapseTreeOrd, CollapsedTree, InvalidMapping};
+use alloc::{vec, vec::Vec};
+use core::{cell::OnceCell, ops::Range};
+
+#[cfg(test)]
+mod tests;
+
+/// Children of each element, in order (compressed adjacency list)
+#[derive(Clone, Debug)]
+struct ChildIndex {
+    /// children of `i` are `list[offsets[i]..offsets[i + 1]]`
+    offsets: Vec<usize>,
+    list: Vec<usize>,
+    roots: Vec<usize>,
+}
+
+impl ChildIndex {
+    fn new<T>(tree: &CollapsedTree<T>) -> Self {
+        let len = tree.elems.len();
+        let mut offsets = vec![0; len + 1];
+        for &parent in tree.mapping.values() {
+            offsets[parent + 1] += 1;
+        }
+        for i in 0..len {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let mut next = offsets.clone();
+        let mut list = vec![0; tree.mapping.len()];
+        let mut roots = Vec::new();
+        for i in 0..len {
+            match tree.mapping.get(&i) {
+                Some(&parent) => {
+                    list[next[parent]] = i;
+                    next[parent] += 1;
+                }
+                None => roots.push(i),
+            }
+        }
+
+        Self {
+            offsets,
+            list,
+            roots,
+        }
+    }
+
+    #[inline]
+    fn children(&self, i: usize) -> &[usize] {
+        &self.list[self.offsets[i]..self.offsets[i + 1]]
+    }
+}
+
+/// Navigation on a [`CollapsedTree`], see [`CollapsedTree::nav`].
+///
+/// Parent queries use the `mapping` directly, child queries use
+/// an index which is built on first use.
+#[derive(Clone, Debug)]
+pub struct TreeNav<'t, T> {
+    tree: &'t CollapsedTree<T>,
+    index: OnceCell<ChildIndex>,
+}
+
+impl<T> CollapsedTree<T> {
+    /// Start navigating the tree.
+    /// The `mapping` must be valid (see [`CollapsedTree::validate`]).
+    #[inline]
+    pub fn nav(&self) -> TreeNav<'_, T> {
+        TreeNav {
+            tree: self,
+            index: OnceCell::new(),
+        }
+    }
+
+    /**
+    Change the layout of the tree to `order` (as if it was collapsed with `order`),
+    by permuting `elems` and rewriting `mapping`, without rebuilding the nodes.
+
+    Siblings must be in order (which is true for every [`CollapseTreeOrd`]).
+
+    Return value:
+    * Err(_): the `mapping` is invalid (see [`CollapsedTree::validate`]), the tree is left unchanged
+    * Ok(perm): the permutation used, `perm[new index] = old index`.
+    **/
+    pub fn reorder(&mut self, order: CollapseTreeOrd) -> Result<Vec<usize>, InvalidMapping> {
+        self.validate()?;
+        let index = ChildIndex::new(self);
+        let len = self.elems.len();
+        let mut perm = Vec::with_capacity(len);
+
+        match order {
+            CollapseTreeOrd::TopDown => {
+                let mut stack: Vec<usize> = index.roots.iter().rev().copied().collect();
+                while let Some(i) = stack.pop() {
+                    perm.push(i);
+                    stack.extend(index.children(i).iter().rev());
+                }
+            }
+            CollapseTreeOrd::BottomUp => {
+                // (element, children already visited)
+                let mut stack: Vec<(usize, bool)> =
+                    index.roots.iter().rev().map(|&i| (i, false)).collect();
+                while let Some((i, visited)) = stack.pop() {
+                    if visited {
+                        perm.push(i);
+                    } else {
+                        stack.push((i, true));
+                        stack.extend(index.children(i).iter().rev().map(|&c| (c, false)));
+                    }
+                }
+            }
+            CollapseTreeOrd::BreadthFirst => {
+                perm.extend_from_slice(&index.roots);
+                let mut pos = 0;
+                while pos < perm.len() {
+                    let i = perm[pos];
+                    perm.extend_from_slice(index.children(i));
+                    pos += 1;
+                }
+            }
+        }
+
+        // a valid mapping reaches every element from a top-level element
+        assert_eq!(perm.len(), len, "reorder: not all elements reached");
+
+        // old index -> new index
+        let mut inv = vec![0; len];
+        for (new, &old) in perm.iter().enumerate() {
+            inv[old] = new;
+        }
+
+        let mut old_elems: Vec<Option<T>> = core::mem::take(&mut self.elems)
+            .into_iter()
+            .map(Some)
+            .collect();
+        self.elems = perm
+            .iter()
+            .map(|&old| old_elems[old].take().unwrap())
+            .collect();
+        self.mapping = core::mem::take(&mut self.mapping)
+            .into_iter()
+            .map(|(child, parent)| (inv[child], inv[parent]))
+            .collect();
+        self.order = order;
+
+        Ok(perm)
+    }
+}
+
+impl<'t, T> TreeNav<'t, T> {
+    #[inline]
+    fn index(&self) -> &ChildIndex {
+        self.index.get_or_init(|| ChildIndex::new(self.tree))
+    }
+
+    /// The navigated tree
+    #[inline]
+    pub fn tree(&self) -> &'t CollapsedTree<T> {
+        self.tree
+    }
+
+    /// The parent of `i`, `None` for top-level elements
+    #[inline]
+    pub fn parent(&self, i: usize) -> Option<usize> {
+        self.tree.mapping.get(&i).copied()
+    }
+
+    /// The direct children of `i`, in order
+    #[inline]
+    pub fn children(&self, i: usize) -> &[usize] {
+        self.index().children(i)
+    }
+
+    /// The top-level elements, in order
+    #[inline]
+    pub fn roots(&self) -> &[usize] {
+        &self.index().roots
+    }
+
+    /// The ancestors of `i`, starting with its parent
+    pub fn ancestors(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut cur = i;
+        core::iter::from_fn(move || {
+            cur = self.parent(cur)?;
+            Some(cur)
+        })
+    }
+
+    /// The number of ancestors of `i`
+    #[inline]
+    pub fn depth(&self, i: usize) -> usize {
+        self.ancestors(i).count()
+    }
+
+    /// The other children of the parent of `i` (or other top-level elements), in order
+    pub fn siblings(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        let all = match self.parent(i) {
+            Some(parent) => self.children(parent),
+            None => self.roots(),
+        };
+        all.iter().copied().filter(move |&j| j != i)
+    }
+
+    /// The number of elements in the subtree of `i`, including `i` itself
+    pub fn subtree_size(&self, i: usize) -> usize {
+        let mut stack = vec![i];
+        let mut ret = 0;
+        while let Some(cur) = stack.pop() {
+            ret += 1;
+            stack.extend_from_slice(self.children(cur));
+        }
+        ret
+    }
+
+    /**
+    The index range of the subtree of `i` (including `i`), based on the `order` of the tree.
+
+    Return value:
+    * None: the subtree isn't contiguous (`BreadthFirst` layouts),
+      or doesn't fit into the tree (the `mapping` doesn't match the `order`).
+    * Some(range): the subtree, which is always contiguous for `TopDown` and `BottomUp` layouts.
+    **/
+    pub fn subtree(&self, i: usize) -> Option<Range<usize>> {
+        let range = match self.tree.order {
+            CollapseTreeOrd::TopDown => i..i.checked_add(self.subtree_size(i))?,
+            CollapseTreeOrd::BottomUp => (i + 1).checked_sub(self.subtree_size(i))?..i + 1,
+            CollapseTreeOrd::BreadthFirst => return None,
+        };
+        if range.end <= self.tree.elems.len() {
+            Some(range)
+        } else {
+            None
+        }
+    }
+}
+
+/**
+Preprocessed lowest common ancestor queries on a [`CollapsedTree`].
+
+Uses an euler tour with a sparse table of minimum depths,
+built in O(n log n), answering [`lca`](LcaIndex::lca) in O(1)
+and [`is_ancestor`](LcaIndex::is_ancestor) in O(1).
+**/
+#[derive(Clone, Debug)]
+pub struct LcaIndex {
+    depth: Vec<usize>,
+    /// top-level element of the tree containing each element
+    root: Vec<usize>,
+    /// position of the first and last occurrence of each element in the euler tour
+    first: Vec<usize>,
+    last: Vec<usize>,
+    /// `table[k][p]` is the element with the minimal depth in `tour[p..p + 2^k]`
+    table: Vec<Vec<usize>>,
+}
+
+impl LcaIndex {
+    /// The `mapping` of the tree must be valid (see [`CollapsedTree::validate`]).
+    pub fn new<T>(tree: &CollapsedTree<T>) -> Self {
+        let len = tree.elems.len();
+        let index = ChildIndex::new(tree);
+        let mut ret = Self {
+            depth: vec![0; len],
+            root: vec![0; len],
+            first: vec![0; len],
+            last: vec![0; len],
+            table: Vec::new(),
+        };
+
+        let mut tour = Vec::with_capacity(2 * len);
+        // (element, position of the next child to visit)
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+        for &root in &index.roots {
+            ret.first[root] = tour.len();
+            tour.push(root);
+            stack.push((root, 0));
+            while let Some((cur, next_child)) = stack.last_mut() {
+                let cur = *cur;
+                match index.children(cur).get(*next_child) {
+                    Some(&child) => {
+                        *next_child += 1;
+                        ret.depth[child] = ret.depth[cur] + 1;
+                        ret.root[child] = root;
+                        ret.first[child] = tour.len();
+                        tour.push(child);
+                        stack.push((child, 0));
+                    }
+                    None => {
+                        stack.pop();
+                        ret.last[cur] = tour.len() - 1;
+                        if let Some(&(parent, _)) = stack.last() {
+                            tour.push(parent);
+                        }
+                    }
+                }
+            }
+            ret.root[root] = root;
+        }
+
+        let mut level = tour;
+        let mut width = 1;
+        while !level.is_empty() {
+            let next: Vec<usize> = (0..level.len().saturating_sub(width))
+                .map(|p| ret.shallower(level[p], level[p + width]))
+                .collect();
+            ret.table.push(level);
+            level = next;
+            width *= 2;
+        }
+
+        ret
+    }
+
+    #[inline]
+    fn shallower(&self, a: usize, b: usize) -> usize {
+        if self.depth[b] < self.depth[a] {
+            b
+        } else {
+            a
+        }
+    }
+
+    /// The number of ancestors of `i`
+    #[inline]
+    pub fn depth(&self, i: usize) -> usize {
+        self.depth[i]
+    }
+
+    /// The lowest common ancestor of `a` and `b` (which is `a` if `a == b`),
+    /// `None` if they belong to different top-level trees.
+    pub fn lca(&self, a: usize, b: usize) -> Option<usize> {
+        if self.root[a] != self.root[b] {
+            return None;
+        }
+        let (l, r) = {
+            let (fa, fb) = (self.first[a], self.first[b]);
+            (fa.min(fb), fa.max(fb) + 1)
+        };
+        let k = (usize::BITS - 1 - (r - l).leading_zeros()) as usize;
+        let level = &self.table[k];
+        Some(self.shallower(level[l], level[r - (1 << k)]))
+    }
+
+    /// Check if `a` is a (strict) ancestor of `b`
+    #[inline]
+    pub fn is_ancestor(&self, a: usize, b: usize) -> bool {
+        a != b && self.first[a] < self.first[b] && self.last[b] <= self.last[a]
+    }
+}
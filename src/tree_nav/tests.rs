@@ -0,0 +1,219 @@
+use super::*;
+use crate::{test_util::XorShift, tree_collapse::CollapseTreeOrd};
+use alloc::vec::Vec;
+
+// Tree used by the tests (`data` of the elements):
+// 0 (1, 2), 3 (4 (6), 5, 7), 8
+fn topdown() -> CollapsedTree<usize> {
+    CollapsedTree {
+        elems: vec![0, 1, 2, 3, 4, 6, 5, 7, 8],
+        mapping: [(1, 0), (2, 0), (4, 3), (5, 4), (6, 3), (7, 3)]
+            .iter()
+            .copied()
+            .collect(),
+        order: CollapseTreeOrd::TopDown,
+    }
+}
+
+fn bottomup() -> CollapsedTree<usize> {
+    CollapsedTree {
+        elems: vec![1, 2, 0, 6, 4, 5, 7, 3, 8],
+        mapping: [(0, 2), (1, 2), (3, 4), (4, 7), (5, 7), (6, 7)]
+            .iter()
+            .copied()
+            .collect(),
+        order: CollapseTreeOrd::BottomUp,
+    }
+}
+
+fn breadthfirst() -> CollapsedTree<usize> {
+    CollapsedTree {
+        elems: vec![0, 3, 8, 1, 2, 4, 5, 7, 6],
+        mapping: [(3, 0), (4, 0), (5, 1), (6, 1), (7, 1), (8, 5)]
+            .iter()
+            .copied()
+            .collect(),
+        order: CollapseTreeOrd::BreadthFirst,
+    }
+}
+
+#[test]
+fn test_nav_topdown() {
+    let tree = topdown();
+    let nav = tree.nav();
+    assert_eq!(nav.roots(), &[0, 3, 8]);
+    assert_eq!(nav.parent(5), Some(4));
+    assert_eq!(nav.parent(3), None);
+    assert_eq!(nav.children(3), &[4, 6, 7]);
+    assert!(nav.children(8).is_empty());
+    assert_eq!(nav.ancestors(5).collect::<Vec<_>>(), vec![4, 3]);
+    assert_eq!(nav.depth(5), 2);
+    assert_eq!(nav.depth(0), 0);
+    assert_eq!(nav.siblings(6).collect::<Vec<_>>(), vec![4, 7]);
+    assert_eq!(nav.siblings(3).collect::<Vec<_>>(), vec![0, 8]);
+    assert_eq!(nav.subtree(3), Some(3..8));
+    assert_eq!(nav.subtree(4), Some(4..6));
+    assert_eq!(nav.subtree(8), Some(8..9));
+    assert_eq!(nav.subtree_size(3), 5);
+}
+
+#[test]
+fn test_nav_bottomup() {
+    let tree = bottomup();
+    let nav = tree.nav();
+    assert_eq!(nav.roots(), &[2, 7, 8]);
+    assert_eq!(nav.children(7), &[4, 5, 6]);
+    assert_eq!(nav.ancestors(3).collect::<Vec<_>>(), vec![4, 7]);
+    assert_eq!(nav.subtree(7), Some(3..8));
+    assert_eq!(nav.subtree(2), Some(0..3));
+    assert_eq!(nav.subtree(4), Some(3..5));
+
+    // top-down mapping, but marked as bottom-up
+    let tree = CollapsedTree {
+        elems: vec![0, 1, 2],
+        mapping: [(1, 0), (2, 0)].iter().copied().collect(),
+        order: CollapseTreeOrd::BottomUp,
+    };
+    assert_eq!(tree.nav().subtree(0), None);
+}
+
+#[test]
+fn test_nav_breadthfirst() {
+    let tree = breadthfirst();
+    let nav = tree.nav();
+    assert_eq!(nav.roots(), &[0, 1, 2]);
+    assert_eq!(nav.children(1), &[5, 6, 7]);
+    assert_eq!(nav.depth(8), 2);
+    assert_eq!(nav.subtree(1), None);
+    assert_eq!(nav.subtree(5), None);
+    assert_eq!(nav.subtree(8), None);
+    assert_eq!(nav.subtree_size(1), 5);
+
+    // the first subtree looks contiguous, but contains the root 1 instead of 4
+    let tree = CollapsedTree {
+        elems: (0..5).collect(),
+        mapping: [(2, 0), (3, 0), (4, 2)].iter().copied().collect(),
+        order: CollapseTreeOrd::BreadthFirst,
+    };
+    assert_eq!(tree.nav().subtree(0), None);
+    assert_eq!(tree.nav().subtree_size(0), 4);
+}
+
+#[test]
+fn test_nav_eq_collapse_meta() {
+    use crate::tree_collapse::{DenseCollapsedTree, TreeMeta};
+    use core::convert::TryFrom;
+
+    for &(ref tree, order) in &[
+        (topdown(), CollapseTreeOrd::TopDown),
+        (bottomup(), CollapseTreeOrd::BottomUp),
+        (breadthfirst(), CollapseTreeOrd::BreadthFirst),
+    ] {
+        let nav = tree.nav();
+        let dense = DenseCollapsedTree::try_from(tree.clone()).expect("valid mapping");
+        let meta = TreeMeta::from_parents(&dense.parents, order);
+        for i in 0..tree.elems.len() {
+            assert_eq!(nav.depth(i), meta.depth[i]);
+            assert_eq!(nav.children(i).len(), meta.child_count[i]);
+            assert_eq!(nav.subtree_size(i), meta.subtree_size[i]);
+        }
+    }
+}
+
+fn brute_lca<T>(tree: &CollapsedTree<T>, a: usize, b: usize) -> Option<usize> {
+    let nav = tree.nav();
+    let a_anc: Vec<usize> = core::iter::once(a).chain(nav.ancestors(a)).collect();
+    let ret = core::iter::once(b)
+        .chain(nav.ancestors(b))
+        .find(|x| a_anc.contains(x));
+    ret
+}
+
+#[test]
+fn test_lca_complex_tree() {
+    for tree in &[topdown(), bottomup(), breadthfirst()] {
+        let lca = LcaIndex::new(tree);
+        for a in 0..tree.elems.len() {
+            for b in 0..tree.elems.len() {
+                assert_eq!(lca.lca(a, b), brute_lca(tree, a, b), "lca({}, {})", a, b);
+            }
+        }
+    }
+
+    let tree = topdown();
+    let lca = LcaIndex::new(&tree);
+    assert_eq!(lca.lca(5, 7), Some(3));
+    assert_eq!(lca.lca(1, 5), None);
+    assert!(lca.is_ancestor(3, 5));
+    assert!(!lca.is_ancestor(5, 3));
+    assert!(!lca.is_ancestor(3, 3));
+    assert!(!lca.is_ancestor(0, 5));
+}
+
+#[test]
+fn test_lca_eq_brute_force() {
+    // pseudo-random forests, each element's parent comes before it
+    let mut rng = XorShift::new(0x9e37_79b9);
+
+    for len in 1..60 {
+        let mapping = (1..len)
+            .filter_map(|child| {
+                let parent = rng.below(child + 1);
+                if parent == child {
+                    None
+                } else {
+                    Some((child, parent))
+                }
+            })
+            .collect();
+        let tree = CollapsedTree {
+            elems: vec![(); len],
+            mapping,
+            order: CollapseTreeOrd::TopDown,
+        };
+        let nav = tree.nav();
+        let lca = LcaIndex::new(&tree);
+        for a in 0..len {
+            assert_eq!(lca.depth(a), nav.depth(a));
+            for b in 0..len {
+                assert_eq!(lca.lca(a, b), brute_lca(&tree, a, b));
+                assert_eq!(lca.is_ancestor(a, b), nav.ancestors(b).any(|x| x == a));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_reorder() {
+    let orders = [
+        (topdown(), CollapseTreeOrd::TopDown),
+        (bottomup(), CollapseTreeOrd::BottomUp),
+        (breadthfirst(), CollapseTreeOrd::BreadthFirst),
+    ];
+    for (from, _) in &orders {
+        for (to, order) in &orders {
+            let mut tree = from.clone();
+            let perm = tree.reorder(*order).unwrap();
+            assert_eq!(&tree, to);
+            for (new, &old) in perm.iter().enumerate() {
+                assert_eq!(tree.elems[new], from.elems[old]);
+            }
+        }
+    }
+
+    let mut tree = topdown();
+    assert_eq!(
+        tree.reorder(CollapseTreeOrd::BottomUp),
+        Ok(vec![1, 2, 0, 5, 4, 6, 7, 3, 8])
+    );
+
+    // elements on a cycle can't be reached, the tree must stay untouched
+    let mut tree = CollapsedTree {
+        elems: vec![10, 11, 12],
+        mapping: [(1, 2), (2, 1)].iter().copied().collect(),
+        order: CollapseTreeOrd::TopDown,
+    };
+    let orig = tree.clone();
+    assert!(tree.reorder(CollapseTreeOrd::BottomUp).is_err());
+    assert_eq!(tree, orig);
+}
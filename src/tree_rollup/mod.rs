@@ -1,3 +1,4 @@
+use crate::tree_collapse::{CollapseTreeOrd, CollapsedTree};
 use alloc::vec::Vec;
 
 #[cfg(test)]
@@ -17,6 +18,26 @@ pub trait ReversableNode: Node {
     fn reverse(&mut self);
 }
 
+impl<T: Node> CollapsedTree<T> {
+    /**
+    Roll up the tree, whatever [`order`](CollapsedTree::order) it was collapsed with.
+    This is the opposite method of [`collapse_tree`](crate::tree_collapse::collapse_tree).
+
+    Only [`Node`] is required: trees which aren't in `BottomUp` layout are
+    [`reorder`](CollapsedTree::reorder)ed first and then rolled up using [`rollup_tree_bottomup`].
+
+    Return value:
+    * None: the `mapping` was invalid.
+    * Some(vec): The rolled-up tree, with only top-level children left at top-level.
+    **/
+    pub fn rollup(mut self) -> Option<Vec<T>> {
+        if self.order != CollapseTreeOrd::BottomUp {
+            self.reorder(CollapseTreeOrd::BottomUp).ok()?;
+        }
+        rollup_tree_bottomup(self.elems, self.mapping).map(Iterator::collect)
+    }
+}
+
 /**
 Roll up a tree given as a flat structure and a mapping {from child to parent}
 into a hierarchical structure.
@@ -40,26 +61,57 @@ where
     M::IntoIter: core::iter::DoubleEndedIterator,
 {
     rollup_tree_intern(input.into_iter().map(Some).collect(), mapping.into_iter())
+        .map(rollup_tree_finish)
 }
 
-fn rollup_tree_intern<T, M>(mut v: Vec<Option<T>>, mapping: M) -> Option<impl Iterator<Item = T>>
+fn rollup_tree_intern<T, M>(mut v: Vec<Option<T>>, mapping: M) -> Option<Vec<Option<T>>>
 where
     T: ReversableNode,
     M: Iterator<Item = (usize, usize)> + core::iter::DoubleEndedIterator,
 {
     for (child_id, parent_id) in mapping.rev() {
-        let mut child: T = core::mem::replace(v.get_mut(child_id)?, None)?;
+        let mut child: T = v.get_mut(child_id)?.take()?;
         child.reverse();
         Node::push_child(v.get_mut(parent_id)?.as_mut()?, child);
     }
-    Some(
-        v.into_iter()
-            .filter_map(core::convert::identity)
-            .map(|mut i| {
-                i.reverse();
-                i
-            }),
-    )
+    Some(v)
+}
+
+fn rollup_tree_finish<T: ReversableNode>(v: Vec<Option<T>>) -> impl Iterator<Item = T> {
+    v.into_iter().flatten().map(|mut i| {
+        i.reverse();
+        i
+    })
+}
+
+/**
+Roll up a tree given as a flat structure in level-order
+(as produced by [`CollapseTreeOrd::BreadthFirst`])
+and a mapping {from child to parent} into a hierarchical structure.
+
+Invariants for the arguments:
+* Any parent must come before it's children inside `input`.
+* Siblings must be in order inside `input`.
+* The largest index in `mapping` should be inside of the bounds of `input`.
+* `mapping` keys (child) must be greater than the associated value (parent)
+* `mapping` keys should be sorted
+
+Return value:
+* None: Detected duplicated usage of id's (probably the `mapping` was invalid).
+* Some(iter): The rolled-up tree, with only top-level children left at top-level.
+**/
+#[inline]
+pub fn rollup_tree_breadthfirst<T, I, M>(input: I, mapping: M) -> Option<impl Iterator<Item = T>>
+where
+    T: ReversableNode,
+    I: IntoIterator<Item = T>,
+    M: IntoIterator<Item = (usize, usize)>,
+    M::IntoIter: core::iter::DoubleEndedIterator,
+{
+    // children always come after their parents, thus the top-down
+    // algorithm completes every element before it is attached to its parent
+    rollup_tree_intern(input.into_iter().map(Some).collect(), mapping.into_iter())
+        .map(rollup_tree_finish)
 }
 
 /**
@@ -84,19 +136,57 @@ where
     M: IntoIterator<Item = (usize, usize)>,
 {
     rollup_tree_bottomup_intern(input.into_iter().map(Some).collect(), mapping.into_iter())
+        .map(|v| v.into_iter().flatten())
 }
 
-fn rollup_tree_bottomup_intern<T, M>(
-    mut v: Vec<Option<T>>,
-    mapping: M,
-) -> Option<impl Iterator<Item = T>>
+fn rollup_tree_bottomup_intern<T, M>(mut v: Vec<Option<T>>, mapping: M) -> Option<Vec<Option<T>>>
 where
     T: Node,
     M: Iterator<Item = (usize, usize)>,
 {
     for (child_id, parent_id) in mapping {
-        let child: T = core::mem::replace(v.get_mut(child_id)?, None)?;
+        let child: T = v.get_mut(child_id)?.take()?;
         Node::push_child(v.get_mut(parent_id)?.as_mut()?, child);
     }
-    Some(v.into_iter().filter_map(core::convert::identity))
+    Some(v)
+}
+
+/**
+Like [`rollup_tree`], but takes a dense parent array
+(see [`DenseCollapsedTree`](crate::tree_collapse::DenseCollapsedTree)) instead of a mapping.
+**/
+#[inline]
+pub fn rollup_tree_dense<T, I>(
+    input: I,
+    parents: &[Option<usize>],
+) -> Option<impl Iterator<Item = T>>
+where
+    T: ReversableNode,
+    I: IntoIterator<Item = T>,
+{
+    rollup_tree_intern(
+        input.into_iter().map(Some).collect(),
+        crate::tree_collapse::parent_pairs(parents),
+    )
+    .map(rollup_tree_finish)
+}
+
+/**
+Like [`rollup_tree_bottomup`], but takes a dense parent array
+(see [`DenseCollapsedTree`](crate::tree_collapse::DenseCollapsedTree)) instead of a mapping.
+**/
+#[inline]
+pub fn rollup_tree_bottomup_dense<T, I>(
+    input: I,
+    parents: &[Option<usize>],
+) -> Option<impl Iterator<Item = T>>
+where
+    T: Node,
+    I: IntoIterator<Item = T>,
+{
+    rollup_tree_bottomup_intern(
+        input.into_iter().map(Some).collect(),
+        crate::tree_collapse::parent_pairs(parents),
+    )
+    .map(|v| v.into_iter().flatten())
 }
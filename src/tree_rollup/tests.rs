@@ -23,7 +23,7 @@ fn test_rollup_tree() {
     mapping.insert(3, 2);
     mapping.insert(4, 1);
 
-    let result: Vec<_> = rollup_tree(input.into_iter(), mapping)
+    let result: Vec<_> = rollup_tree(input, mapping)
         .expect("valid mapping")
         .collect();
     assert_eq!(result, vec![Element(5), Element(5)]);
@@ -71,7 +71,7 @@ fn test_rollup_complex_tree() {
     ];
     let mapping = vec![(1, 0), (2, 0), (4, 3), (5, 3), (6, 4), (7, 3)];
 
-    let result: Vec<_> = rollup_tree(input.into_iter(), mapping)
+    let result: Vec<_> = rollup_tree(input, mapping)
         .expect("invalid mapping")
         .collect();
     assert_eq!(
@@ -112,7 +112,7 @@ fn test_rollup_bottomup_complex_tree() {
     ];
     let mapping = vec![(0, 2), (1, 2), (3, 5), (4, 5), (5, 7), (6, 7)];
 
-    let result: Vec<_> = rollup_tree_bottomup(input.into_iter(), mapping)
+    let result: Vec<_> = rollup_tree_bottomup(input, mapping)
         .expect("invalid mapping")
         .collect();
 
@@ -137,3 +137,106 @@ fn test_rollup_bottomup_complex_tree() {
         ]
     );
 }
+
+#[test]
+fn test_rollup_breadthfirst_complex_tree() {
+    let input = vec![
+        CplxElement::new(0),
+        CplxElement::new(3),
+        CplxElement::new(8),
+        CplxElement::new(1),
+        CplxElement::new(2),
+        CplxElement::new(4),
+        CplxElement::new(5),
+        CplxElement::new(7),
+        CplxElement::new(6),
+    ];
+    let mapping = vec![(3, 0), (4, 0), (5, 1), (6, 1), (7, 1), (8, 5)];
+
+    let result: Vec<_> = rollup_tree_breadthfirst(input, mapping)
+        .expect("invalid mapping")
+        .collect();
+    assert_eq!(
+        result,
+        vec![
+            CplxElement {
+                data: 0,
+                children: vec![CplxElement::new(1), CplxElement::new(2)],
+            },
+            CplxElement {
+                data: 3,
+                children: vec![
+                    CplxElement {
+                        data: 4,
+                        children: vec![CplxElement::new(6)],
+                    },
+                    CplxElement::new(5),
+                    CplxElement::new(7),
+                ],
+            },
+            CplxElement::new(8),
+        ]
+    );
+}
+
+#[test]
+fn test_rollup_dense() {
+    let input = vec![Element(0), Element(1), Element(2), Element(3), Element(4)];
+    let parents = [None, Some(0), None, Some(2), Some(1)];
+    let result: Vec<_> = rollup_tree_dense(input, &parents)
+        .expect("valid mapping")
+        .collect();
+    assert_eq!(result, vec![Element(5), Element(5)]);
+
+    let input = vec![Element(1), Element(2), Element(3)];
+    let parents = [Some(2), Some(2), None];
+    let result: Vec<_> = rollup_tree_bottomup_dense(input, &parents)
+        .expect("valid mapping")
+        .collect();
+    assert_eq!(result, vec![Element(6)]);
+
+    assert!(rollup_tree_dense(vec![Element(0)], &[Some(1)]).is_none());
+}
+
+/// only implements `Node`, records the ids of its direct children
+#[derive(Debug, PartialEq, Eq)]
+struct Seq(usize, Vec<usize>);
+
+impl Node for Seq {
+    fn push_child(&mut self, child: Seq) {
+        self.1.push(child.0);
+    }
+}
+
+#[test]
+fn test_collapsed_tree_rollup_node_only() {
+    use crate::tree_collapse::CollapseTreeOrd;
+
+    // 0 (1, 2), 3 (4 (6), 5, 7), 8 in top-down layout
+    let tree = CollapsedTree {
+        elems: [0, 1, 2, 3, 4, 6, 5, 7, 8]
+            .iter()
+            .map(|&i| Seq(i, vec![]))
+            .collect(),
+        mapping: [(1, 0), (2, 0), (4, 3), (5, 4), (6, 3), (7, 3)]
+            .iter()
+            .copied()
+            .collect(),
+        order: CollapseTreeOrd::TopDown,
+    };
+    assert_eq!(
+        tree.rollup(),
+        Some(vec![
+            Seq(0, vec![1, 2]),
+            Seq(3, vec![4, 5, 7]),
+            Seq(8, vec![])
+        ])
+    );
+
+    let tree = CollapsedTree {
+        elems: vec![Seq(0, vec![]), Seq(1, vec![])],
+        mapping: [(0, 1), (1, 0)].iter().copied().collect(),
+        order: CollapseTreeOrd::BreadthFirst,
+    };
+    assert_eq!(tree.rollup(), None);
+}
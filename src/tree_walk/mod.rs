@@ -0,0 +1,214 @@
+use crate::tree_collapse::Node;
+use alloc::{collections::VecDeque, vec::Vec};
+
+#[cfg(test)]
+mod tests;
+
+/// An element reached by [`WalkTreeIT`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WalkItem<T> {
+    pub elem: T,
+    /// index the element would get in [`collapse_tree`](crate::tree_collapse::collapse_tree)
+    pub index: usize,
+    /// index of the parent, `None` for top-level elements
+    pub parent: Option<usize>,
+    /// number of ancestors
+    pub depth: usize,
+}
+
+/// An element reached by [`WalkTreeBottomUpIT`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BottomUpWalkItem<T> {
+    pub elem: T,
+    /// index the element would get in [`collapse_tree`](crate::tree_collapse::collapse_tree)
+    pub index: usize,
+    /// indices of the direct children, which were all yielded before;
+    /// parents are only reached after their children, thus the mapping
+    /// is reported from the parent side
+    pub children: Vec<usize>,
+    /// number of ancestors
+    pub depth: usize,
+}
+
+/// Lazy pre-order or level-order walk over a tree,
+/// yielding the elements in the order of [`collapse_tree`](crate::tree_collapse::collapse_tree).
+#[must_use]
+pub struct WalkTreeIT<T: Node, InIter> {
+    input: InIter,
+    /// (children of parent, parent id, parent depth);
+    /// used as stack for pre-order and as queue for level-order
+    pending: VecDeque<(T::ChildrenIter, usize, usize)>,
+    breadthfirst: bool,
+    input_done: bool,
+    next_index: usize,
+}
+
+impl<T: Node, InIter: Iterator<Item = T>> WalkTreeIT<T, InIter> {
+    fn reach(&mut self, mut elem: T, parent: Option<(usize, usize)>) -> WalkItem<T> {
+        let index = self.next_index;
+        self.next_index += 1;
+        let depth = parent.map_or(0, |(_, depth)| depth + 1);
+        self.pending.push_back((elem.take_children(), index, depth));
+        WalkItem {
+            elem,
+            index,
+            parent: parent.map(|(parent, _)| parent),
+            depth,
+        }
+    }
+}
+
+impl<T: Node, InIter: Iterator<Item = T>> Iterator for WalkTreeIT<T, InIter> {
+    type Item = WalkItem<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // level-order: all top-level elements come first
+            if !self.input_done && (self.breadthfirst || self.pending.is_empty()) {
+                match self.input.next() {
+                    Some(elem) => return Some(self.reach(elem, None)),
+                    None => self.input_done = true,
+                }
+            }
+
+            let cur = if self.breadthfirst {
+                self.pending.front_mut()
+            } else {
+                self.pending.back_mut()
+            }?;
+            let parent = (cur.1, cur.2);
+            match cur.0.next() {
+                Some(elem) => return Some(self.reach(elem, Some(parent))),
+                None => {
+                    if self.breadthfirst {
+                        self.pending.pop_front();
+                    } else {
+                        self.pending.pop_back();
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T: Node, InIter: Iterator<Item = T>> core::iter::FusedIterator for WalkTreeIT<T, InIter> {}
+
+/// Lazy post-order walk over a tree,
+/// yielding the elements in the order of [`collapse_tree`](crate::tree_collapse::collapse_tree)
+/// with [`CollapseTreeOrd::BottomUp`](crate::tree_collapse::CollapseTreeOrd::BottomUp).
+#[must_use]
+pub struct WalkTreeBottomUpIT<T: Node, InIter> {
+    input: core::iter::Fuse<InIter>,
+    /// (parent, children of parent, start of its finished children in `finished`)
+    stack: Vec<(T, T::ChildrenIter, usize)>,
+    /// ids of yielded elements whose parent isn't yielded yet
+    finished: Vec<usize>,
+    next_index: usize,
+}
+
+impl<T: Node, InIter: Iterator<Item = T>> Iterator for WalkTreeBottomUpIT<T, InIter> {
+    type Item = BottomUpWalkItem<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let cur = match self.stack.last_mut() {
+                Some(cur) => cur,
+                None => {
+                    let mut elem = self.input.next()?;
+                    let children = elem.take_children();
+                    self.stack.push((elem, children, self.finished.len()));
+                    continue;
+                }
+            };
+            match cur.1.next() {
+                Some(mut elem) => {
+                    let children = elem.take_children();
+                    self.stack.push((elem, children, self.finished.len()));
+                }
+                None => {
+                    let (elem, _, subs_start) = self.stack.pop().unwrap();
+                    let index = self.next_index;
+                    self.next_index += 1;
+                    let children = self.finished.drain(subs_start..).collect();
+                    if !self.stack.is_empty() {
+                        self.finished.push(index);
+                    }
+                    return Some(BottomUpWalkItem {
+                        elem,
+                        index,
+                        children,
+                        depth: self.stack.len(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl<T: Node, InIter: Iterator<Item = T>> core::iter::FusedIterator
+    for WalkTreeBottomUpIT<T, InIter>
+{
+}
+
+/**
+Walk a tree in pre-order, lazily yielding each element together with
+its index, parent index and depth, as [`collapse_tree`](crate::tree_collapse::collapse_tree)
+with [`CollapseTreeOrd::TopDown`](crate::tree_collapse::CollapseTreeOrd::TopDown) would assign them.
+
+Only the path to the current element is held in memory.
+**/
+#[inline]
+pub fn walk_tree_topdown<T, I>(input: I) -> WalkTreeIT<T, I::IntoIter>
+where
+    T: Node,
+    I: IntoIterator<Item = T>,
+{
+    WalkTreeIT {
+        input: input.into_iter(),
+        pending: VecDeque::new(),
+        breadthfirst: false,
+        input_done: false,
+        next_index: 0,
+    }
+}
+
+/**
+Walk a tree in level-order, lazily yielding each element together with
+its index, parent index and depth, as [`collapse_tree`](crate::tree_collapse::collapse_tree)
+with [`CollapseTreeOrd::BreadthFirst`](crate::tree_collapse::CollapseTreeOrd::BreadthFirst) would assign them.
+**/
+#[inline]
+pub fn walk_tree_breadthfirst<T, I>(input: I) -> WalkTreeIT<T, I::IntoIter>
+where
+    T: Node,
+    I: IntoIterator<Item = T>,
+{
+    WalkTreeIT {
+        input: input.into_iter(),
+        pending: VecDeque::new(),
+        breadthfirst: true,
+        input_done: false,
+        next_index: 0,
+    }
+}
+
+/**
+Walk a tree in post-order, lazily yielding each element together with
+its index, the indices of its children and its depth, as [`collapse_tree`](crate::tree_collapse::collapse_tree)
+with [`CollapseTreeOrd::BottomUp`](crate::tree_collapse::CollapseTreeOrd::BottomUp) would assign them.
+
+Only the path to the current element is held in memory.
+**/
+#[inline]
+pub fn walk_tree_bottomup<T, I>(input: I) -> WalkTreeBottomUpIT<T, I::IntoIter>
+where
+    T: Node,
+    I: IntoIterator<Item = T>,
+{
+    WalkTreeBottomUpIT {
+        input: input.into_iter().fuse(),
+        stack: Vec::new(),
+        finished: Vec::new(),
+        next_index: 0,
+    }
+}
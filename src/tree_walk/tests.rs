@@ -0,0 +1,120 @@
+use super::*;
+use crate::test_util::{complex_input, CplxElement};
+use crate::tree_collapse::{collapse_tree_with_meta, CollapseTreeOrd, TreeMeta};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+fn check_eq_collapse(
+    order: CollapseTreeOrd,
+    items: Vec<(CplxElement, usize, usize)>,
+    mapping: BTreeMap<usize, usize>,
+) {
+    let (tree, meta): (_, TreeMeta) = collapse_tree_with_meta(complex_input().into_iter(), order);
+    assert_eq!(tree.mapping, mapping);
+    for (elem, index, depth) in items {
+        assert_eq!(tree.elems[index], elem);
+        assert_eq!(meta.depth[index], depth);
+    }
+}
+
+#[test]
+fn test_walk_topdown() {
+    let items: Vec<_> = walk_tree_topdown(complex_input()).collect();
+    let data: Vec<_> = items.iter().map(|i| i.elem.data).collect();
+    assert_eq!(data, vec![0, 1, 2, 3, 4, 6, 5, 7, 8]);
+    assert_eq!(
+        items[5],
+        WalkItem {
+            elem: CplxElement::new(6),
+            index: 5,
+            parent: Some(4),
+            depth: 2,
+        }
+    );
+
+    let mapping = items
+        .iter()
+        .filter_map(|i| i.parent.map(|p| (i.index, p)))
+        .collect();
+    let items = items
+        .into_iter()
+        .map(|i| (i.elem, i.index, i.depth))
+        .collect();
+    check_eq_collapse(CollapseTreeOrd::TopDown, items, mapping);
+}
+
+#[test]
+fn test_walk_breadthfirst() {
+    let items: Vec<_> = walk_tree_breadthfirst(complex_input()).collect();
+    let data: Vec<_> = items.iter().map(|i| i.elem.data).collect();
+    assert_eq!(data, vec![0, 3, 8, 1, 2, 4, 5, 7, 6]);
+
+    let mapping = items
+        .iter()
+        .filter_map(|i| i.parent.map(|p| (i.index, p)))
+        .collect();
+    let items = items
+        .into_iter()
+        .map(|i| (i.elem, i.index, i.depth))
+        .collect();
+    check_eq_collapse(CollapseTreeOrd::BreadthFirst, items, mapping);
+}
+
+#[test]
+fn test_walk_bottomup() {
+    let items: Vec<_> = walk_tree_bottomup(complex_input()).collect();
+    let data: Vec<_> = items.iter().map(|i| i.elem.data).collect();
+    assert_eq!(data, vec![1, 2, 0, 6, 4, 5, 7, 3, 8]);
+    assert_eq!(items[7].children, vec![4, 5, 6]);
+
+    let mapping = items
+        .iter()
+        .flat_map(|i| i.children.iter().map(move |&c| (c, i.index)))
+        .collect();
+    let items = items
+        .into_iter()
+        .map(|i| (i.elem, i.index, i.depth))
+        .collect();
+    check_eq_collapse(CollapseTreeOrd::BottomUp, items, mapping);
+}
+
+#[test]
+fn test_walk_lazy() {
+    // the walk only pulls as many top-level elements as needed
+    let mut pulled = 0;
+    let mut it = walk_tree_topdown(complex_input().into_iter().inspect(|_| pulled += 1));
+    assert_eq!(it.next().map(|i| i.elem.data), Some(0));
+    assert_eq!(it.nth(2).map(|i| i.elem.data), Some(3));
+    drop(it);
+    assert_eq!(pulled, 2);
+}
+
+#[test]
+fn test_walk_fused() {
+    // input which continues after returning `None`
+    let unfused = || {
+        let mut n = 0;
+        core::iter::from_fn(move || {
+            n += 1;
+            if n % 2 == 0 {
+                None
+            } else {
+                Some(CplxElement::new(n))
+            }
+        })
+    };
+
+    let mut it = walk_tree_topdown(unfused());
+    assert_eq!(it.next().map(|i| i.elem.data), Some(1));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+
+    let mut it = walk_tree_breadthfirst(unfused());
+    assert_eq!(it.next().map(|i| i.elem.data), Some(1));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+
+    let mut it = walk_tree_bottomup(unfused());
+    assert_eq!(it.next().map(|i| i.elem.data), Some(1));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+}